@@ -1,8 +1,9 @@
 use solana_program::{
-    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg,
+    program_error::PrintProgramError, pubkey::Pubkey,
 };
 
-use crate::processor::Processor;
+use crate::{error::NFTStakingContractError, processor::Processor};
 
 entrypoint!(process_instruction);
 
@@ -13,7 +14,12 @@ fn process_instruction(
 ) -> ProgramResult {
     msg!("Metality: Entrypoint");
 
-    Processor::unpack_and_process_instruction(program_id, accounts, instruction_data)?;
+    if let Err(error) =
+        Processor::unpack_and_process_instruction(program_id, accounts, instruction_data)
+    {
+        error.print::<NFTStakingContractError>();
+        return Err(error);
+    }
 
     Ok(())
 }
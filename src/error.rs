@@ -1,7 +1,12 @@
-use solana_program::program_error::ProgramError;
+use num_derive::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
 use thiserror::Error;
 
-#[derive(Error, Debug, Copy, Clone)]
+#[derive(Error, Debug, Copy, Clone, PartialEq, FromPrimitive)]
 pub enum NFTStakingContractError {
     #[error("Invalid Instruction")]
     InvalidInstruction,
@@ -12,29 +17,281 @@ pub enum NFTStakingContractError {
     #[error("Incorrect token ATA Owner")]
     IncorrectATAOwner,
 
-    #[error("Invalid token ata, mint mismatch")]
-    InvalidTokenATA,
+    #[error("Token account not owned by the token program")]
+    TokenAccountNotOwnedByTokenProgram,
 
-    #[error("Not Admin")]
-    NotAdmin,
+    #[error("Reward mint must have 8 decimals")]
+    InvalidRewardMintDecimals,
+
+    #[error("Token account mint is not the reward mint")]
+    WrongRewardMint,
 
-    #[error("Equality Mismatch")]
-    EqualityMismatch,
+    #[error("NFT token account must hold exactly one token")]
+    NftAmountNotOne,
 
-    #[error("Math Error")]
-    MathError,
+    #[error("NFT mint must have 0 decimals")]
+    NftDecimalsNotZero,
+
+    #[error("NFT token account mint does not match NFT mint")]
+    NftMintMismatch,
+
+    #[error("Not Admin")]
+    NotAdmin,
 
     #[error("User Not Signer")]
     UserNotSigner,
 
-    #[error("Invalid State Account")]
-    InvalidStateAccount,
+    #[error("PDA account does not match derived address")]
+    PdaMismatch,
+
+    #[error("State account does not belong to user")]
+    UserMismatch,
+
+    #[error("NFT state does not belong to user base state")]
+    UserBaseStateMismatch,
+
+    #[error("Reward token account does not match user base state")]
+    RewardAtaMismatch,
+
+    #[error("NFT token account does not match NFT state")]
+    NftAtaMismatch,
+
+    #[error("Reward vault does not match platform state")]
+    RewardVaultMismatch,
+
+    #[error("State account not owned by program")]
+    StateAccountNotOwnedByProgram,
+
+    #[error("State account not initialized")]
+    StateAccountNotInitialized,
+
+    #[error("Metadata account does not match NFT mint")]
+    MetadataAccountMismatch,
+
+    #[error("NFT name does not contain a rarity code")]
+    MissingRarityCode,
+
+    #[error("Unknown NFT rarity code")]
+    UnknownRarityCode,
+
+    #[error("Total coin emission overflow")]
+    CoinEmissionOverflow,
+
+    #[error("Reward accumulation interval is zero")]
+    ZeroRewardAccumulationInterval,
+
+    #[error("Reward generated overflow")]
+    RewardGeneratedOverflow,
+
+    #[error("Reward per share overflow")]
+    RewardPerShareOverflow,
+
+    #[error("Pending reward overflow")]
+    PendingRewardOverflow,
+
+    #[error("Reward debt exceeds accrued reward")]
+    RewardDebtUnderflow,
+
+    #[error("Reward debt overflow")]
+    RewardDebtOverflow,
+
+    #[error("Total reward claimed overflow")]
+    RewardClaimedOverflow,
+
+    #[error("Staked NFT count overflow")]
+    StakedCountOverflow,
+
+    #[error("Staked NFT count underflow")]
+    StakedCountUnderflow,
+
+    #[error("NFT points overflow")]
+    NftPointsOverflow,
+
+    #[error("NFT points underflow")]
+    NftPointsUnderflow,
+
+    #[error("Lamports overflow")]
+    LamportsOverflow,
+
+    #[error("Invalid token program")]
+    InvalidTokenProgram,
+
+    #[error("Invalid system program")]
+    InvalidSystemProgram,
+
+    #[error("Invalid associated token account program")]
+    InvalidAssociatedTokenProgram,
+
+    #[error("Invalid metadata program")]
+    InvalidMetadataProgram,
+
+    #[error("Invalid rent sysvar")]
+    InvalidRentSysvar,
+
+    #[error("Reward token account is not the user's associated token account")]
+    RewardAtaNotCanonical,
+
+    #[error("State account is not rent exempt")]
+    StateAccountNotRentExempt,
+
+    #[error("State account has been closed")]
+    StateAccountClosed,
+
+    #[error("Lock duration does not match a lock tier")]
+    InvalidLockDuration,
+
+    #[error("Invalid lock tiers")]
+    InvalidLockTiers,
+
+    #[error("NFT is still locked")]
+    NftLocked,
+
+    #[error("Unstake already requested for NFT")]
+    UnstakeAlreadyRequested,
+
+    #[error("Unstake not requested for NFT")]
+    UnstakeNotRequested,
+
+    #[error("Unstake cooldown has not elapsed")]
+    UnstakeCooldownActive,
+
+    #[error("Invalid unstake cooldown")]
+    InvalidUnstakeCooldown,
+
+    #[error("Breaking a lock early is disabled")]
+    EarlyExitDisabled,
+
+    #[error("Invalid early exit penalty")]
+    InvalidEarlyExitPenalty,
 
-    #[error("Bool Equality Mismatch")]
-    BoolEqualityMismatch,
+    #[error("Penalty treasury does not match platform state")]
+    PenaltyTreasuryMismatch,
 
-    #[error("Invalid NFT Metadata")]
-    InvalidNFTMetadata,
+    #[error("Early exit penalty overflow")]
+    EarlyExitPenaltyOverflow,
+
+    #[error("Invalid warm-up period")]
+    InvalidWarmUpPeriod,
+
+    #[error("Invalid loyalty curve")]
+    InvalidLoyaltyCurve,
+
+    #[error("Invalid set completion bonus")]
+    InvalidSetBonus,
+
+    #[error("Mint points override does not match derived address")]
+    MintPointsOverrideMismatch,
+
+    #[error("Invalid mint points override")]
+    InvalidMintPointsOverride,
+
+    #[error("Invalid mint list")]
+    InvalidMintList,
+
+    #[error("Mint list entry does not match derived address")]
+    MintListEntryMismatch,
+
+    #[error("NFT mint is not on the allowlist")]
+    MintNotAllowlisted,
+
+    #[error("NFT mint is on the denylist")]
+    MintDenylisted,
+
+    #[error("NFT mint is not on the denylist")]
+    MintNotDenylisted,
+
+    #[error("Rarity proof required")]
+    MissingRarityProof,
+
+    #[error("Invalid rarity proof")]
+    InvalidRarityProof,
+
+    #[error("State account does not belong to platform")]
+    PlatformMismatch,
+
+    #[error("User state account does not match derived address")]
+    UserStateAddressMismatch,
+
+    #[error("Partner reward stream limit reached")]
+    PartnerRewardStreamLimitReached,
+
+    #[error("Invalid partner reward stream")]
+    InvalidPartnerRewardStream,
+
+    #[error("Partner reward vault does not match platform state")]
+    PartnerRewardVaultMismatch,
+
+    #[error("Reward mint authority is not the platform PDA")]
+    RewardMintAuthorityMismatch,
+
+    #[error("Minted rewards mode is disabled")]
+    MintedRewardsDisabled,
+
+    #[error("Invalid reward mint cap")]
+    InvalidRewardMintCap,
+
+    #[error("Invalid protocol fee")]
+    InvalidProtocolFee,
+
+    #[error("Protocol fee treasury does not match platform state")]
+    ProtocolFeeTreasuryMismatch,
+
+    #[error("Protocol fee overflow")]
+    ProtocolFeeOverflow,
+
+    #[error("Invalid stake fee")]
+    InvalidStakeFee,
+
+    #[error("SOL treasury does not match derived address")]
+    SolTreasuryMismatch,
+
+    #[error("Insufficient SOL treasury balance")]
+    InsufficientSolTreasuryBalance,
+
+    #[error("Invalid vesting period")]
+    InvalidVestingPeriod,
+
+    #[error("Vesting schedule does not match derived address")]
+    VestingScheduleMismatch,
+
+    #[error("Vesting amount overflow")]
+    VestingOverflow,
+
+    #[error("Invalid claim window")]
+    InvalidClaimWindow,
+
+    #[error("Reward expiry is disabled")]
+    RewardExpiryDisabled,
+
+    #[error("No staked NFT points to distribute to")]
+    NoStakedNftPoints,
+
+    #[error("Bonus amount overflow")]
+    BonusOverflow,
+
+    #[error("Invalid boost window")]
+    InvalidBoostWindow,
+
+    #[error("Boost window limit reached")]
+    BoostWindowLimitReached,
+
+    #[error("Boost window overlaps an existing window")]
+    BoostWindowOverlap,
+
+    #[error("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[error("Reward mint cap exceeded")]
+    RewardMintCapExceeded,
+
+    #[error("Bonus distribution requires a vault funded pool")]
+    BonusRequiresVaultRewards,
+
+    #[error("Unstake cooldown is enabled, request the unstake instead")]
+    UnstakeCooldownEnabled,
+
+    #[error("Every other staked user NFT state must be passed once")]
+    IncompleteUserNftStates,
 }
 
 impl From<NFTStakingContractError> for ProgramError {
@@ -42,3 +299,22 @@ impl From<NFTStakingContractError> for ProgramError {
         ProgramError::Custom(e as u32)
     }
 }
+
+impl<T> DecodeError<T> for NFTStakingContractError {
+    fn type_of() -> &'static str {
+        "NFTStakingContractError"
+    }
+}
+
+impl PrintProgramError for NFTStakingContractError {
+    fn print<E>(&self)
+    where
+        E: 'static
+            + std::error::Error
+            + DecodeError<E>
+            + PrintProgramError
+            + num_traits::FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
@@ -0,0 +1,521 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::{LOCK_TIERS, MAX_POOL_NAME_LEN, RARITY_TIERS};
+
+pub const PROGRAM_DATA_LOG_PREFIX: &str = "Program data: ";
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct Staked {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub nft_points: u64,
+    pub user_total_staked_nfts: u64,
+    pub user_total_nft_points: u64,
+    pub platform_total_staked_nfts: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct UnstakeRequested {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub nft_points: u64,
+    pub user_total_staked_nfts: u64,
+    pub user_total_nft_points: u64,
+    pub platform_total_staked_nfts: u64,
+    pub cooldown_end_timestamp: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct Unstaked {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub nft_points: u64,
+    pub user_total_staked_nfts: u64,
+    pub user_total_nft_points: u64,
+    pub platform_total_staked_nfts: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct UnstakeCompleted {
+    pub user: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct Claimed {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_reward_ata: Pubkey,
+    pub reward: u64,
+    pub token_amount: u64,
+    pub total_reward_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PoolUpdated {
+    pub platform: Pubkey,
+    pub reward_generated: u64,
+    pub reward_per_share: u64,
+    pub total_staked_nfts: u64,
+    pub last_updated: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ConfigChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub coin_emission_percentage: u8,
+    pub coin_emission_distribution_in_sec: u64,
+    pub reward_accumulation_in_sec: u64,
+    pub total_coin_emission: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RewardDestinationChanged {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub old_reward_destination: Pubkey,
+    pub new_reward_destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct UserAccountClosed {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub total_reward_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct LockTiersChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub durations_in_sec: [u64; LOCK_TIERS],
+    pub multipliers: [u16; LOCK_TIERS],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct UnstakeCooldownChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub cooldown_in_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct EarlyExitPenaltyPaid {
+    pub user: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub pending_reward: u64,
+    pub forfeited_reward: u64,
+    pub to_treasury: bool,
+    pub reward_per_share: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct EarlyExitPenaltyChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub percentage: u8,
+    pub to_treasury: bool,
+    pub penalty_treasury_ata: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct NftPointsActivated {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub activated_nft_points: u64,
+    pub user_total_nft_points: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct WarmUpPeriodChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub warm_up_period_in_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct LoyaltyPointsUpdated {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub base_nft_points: u64,
+    pub nft_points: u64,
+    pub user_total_nft_points: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct LoyaltyCurveChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub step_in_sec: u64,
+    pub step_percentage: u16,
+    pub max_percentage: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct SetBonusUpdated {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub rarity_counts: [u64; RARITY_TIERS],
+    pub set_bonus_points: u64,
+    pub user_total_nft_points: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct SetBonusChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub multiplier: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct MintPointsOverrideChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub mint_points_override: Pubkey,
+    pub nft_mint: Pubkey,
+    pub points: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct MintPointsOverrideRemoved {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub mint_points_override: Pubkey,
+    pub nft_mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct MintListModeChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub mint_list_mode: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct MintListEntryChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub mint_list_entry: Pubkey,
+    pub nft_mint: Pubkey,
+    pub list: u8,
+    pub listed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ForceUnstaked {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub nft_points: u64,
+    pub user_total_staked_nfts: u64,
+    pub user_total_nft_points: u64,
+    pub platform_total_staked_nfts: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RarityMerkleRootChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub root: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PartnerRewardStreamAdded {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub stream: u8,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub emission_per_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PartnerRewardEmissionChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub stream: u8,
+    pub emission_per_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PartnerRewardStreamRemoved {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub stream: u8,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PartnerRewardClaimed {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub stream: u8,
+    pub mint: Pubkey,
+    pub user_token_account: Pubkey,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RewardMintCapChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub reward_mint_cap: u64,
+    pub total_reward_minted: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RewardMintCapReached {
+    pub platform: Pubkey,
+    pub requested_token_amount: u64,
+    pub minted_token_amount: u64,
+    pub total_reward_minted: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ProtocolFeeChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ProtocolFeeTreasuryChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub protocol_fee_treasury_ata: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct StakeFeeChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub stake_fee_lamports: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct TreasurySolWithdrawn {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub remaining_lamports: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct VestingPeriodChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub vesting_period_in_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RewardVested {
+    pub user: Pubkey,
+    pub vesting_schedule: Pubkey,
+    pub token_amount: u64,
+    pub locked_amount: u64,
+    pub unlocked_amount: u64,
+    pub end_timestamp: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct VestedRewardWithdrawn {
+    pub user: Pubkey,
+    pub vesting_schedule: Pubkey,
+    pub user_reward_ata: Pubkey,
+    pub token_amount: u64,
+    pub locked_amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ClaimWindowChanged {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub claim_window_in_sec: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct RewardsExpired {
+    pub platform: Pubkey,
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub expired_reward: u64,
+    pub remaining_reward: u64,
+    pub recycled_reward: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct BonusDistributed {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub reward_per_share_increase: u64,
+    pub total_nft_points: u64,
+    pub recycled_reward: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct BoostWindowAdded {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub window: u8,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub multiplier: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct BoostWindowRemoved {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub window: u8,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub multiplier: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct NftLocked {
+    pub user: Pubkey,
+    pub user_nft_state: Pubkey,
+    pub nft_mint: Pubkey,
+    pub unlock_timestamp: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct PoolCreated {
+    pub platform: Pubkey,
+    pub admin: Pubkey,
+    pub pool_name: [u8; MAX_POOL_NAME_LEN],
+    pub reward_mode: u8,
+    pub reward_mint_cap: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct ProtocolFeePaid {
+    pub user: Pubkey,
+    pub user_base_state: Pubkey,
+    pub protocol_fee_treasury_ata: Pubkey,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub struct StakeFeePaid {
+    pub user: Pubkey,
+    pub sol_treasury: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone)]
+pub enum NFTStakingContractEvent {
+    Staked(Staked),
+    Unstaked(Unstaked),
+    Claimed(Claimed),
+    PoolUpdated(PoolUpdated),
+    ConfigChanged(ConfigChanged),
+    RewardDestinationChanged(RewardDestinationChanged),
+    UserAccountClosed(UserAccountClosed),
+    LockTiersChanged(LockTiersChanged),
+    UnstakeRequested(UnstakeRequested),
+    UnstakeCooldownChanged(UnstakeCooldownChanged),
+    EarlyExitPenaltyPaid(EarlyExitPenaltyPaid),
+    EarlyExitPenaltyChanged(EarlyExitPenaltyChanged),
+    NftPointsActivated(NftPointsActivated),
+    WarmUpPeriodChanged(WarmUpPeriodChanged),
+    LoyaltyPointsUpdated(LoyaltyPointsUpdated),
+    LoyaltyCurveChanged(LoyaltyCurveChanged),
+    SetBonusUpdated(SetBonusUpdated),
+    SetBonusChanged(SetBonusChanged),
+    MintPointsOverrideChanged(MintPointsOverrideChanged),
+    MintPointsOverrideRemoved(MintPointsOverrideRemoved),
+    MintListModeChanged(MintListModeChanged),
+    MintListEntryChanged(MintListEntryChanged),
+    ForceUnstaked(ForceUnstaked),
+    RarityMerkleRootChanged(RarityMerkleRootChanged),
+    PartnerRewardStreamAdded(PartnerRewardStreamAdded),
+    PartnerRewardEmissionChanged(PartnerRewardEmissionChanged),
+    PartnerRewardClaimed(PartnerRewardClaimed),
+    RewardMintCapChanged(RewardMintCapChanged),
+    RewardMintCapReached(RewardMintCapReached),
+    ProtocolFeeChanged(ProtocolFeeChanged),
+    ProtocolFeeTreasuryChanged(ProtocolFeeTreasuryChanged),
+    StakeFeeChanged(StakeFeeChanged),
+    TreasurySolWithdrawn(TreasurySolWithdrawn),
+    VestingPeriodChanged(VestingPeriodChanged),
+    RewardVested(RewardVested),
+    VestedRewardWithdrawn(VestedRewardWithdrawn),
+    ClaimWindowChanged(ClaimWindowChanged),
+    RewardsExpired(RewardsExpired),
+    BonusDistributed(BonusDistributed),
+    BoostWindowAdded(BoostWindowAdded),
+    BoostWindowRemoved(BoostWindowRemoved),
+    UnstakeCompleted(UnstakeCompleted),
+    NftLocked(NftLocked),
+    PoolCreated(PoolCreated),
+    ProtocolFeePaid(ProtocolFeePaid),
+    StakeFeePaid(StakeFeePaid),
+    PartnerRewardStreamRemoved(PartnerRewardStreamRemoved),
+}
+
+impl NFTStakingContractEvent {
+    pub fn emit(&self) -> ProgramResult {
+        let data = self.try_to_vec()?;
+
+        sol_log_data(&[&data]);
+
+        Ok(())
+    }
+
+    pub fn from_log(log: &str) -> Option<Self> {
+        let encoded = log
+            .strip_prefix(PROGRAM_DATA_LOG_PREFIX)?
+            .split_whitespace()
+            .next()?;
+
+        let data = base64::decode(encoded).ok()?;
+
+        Self::try_from_slice(&data).ok()
+    }
+
+    pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Vec<Self> {
+        logs.iter()
+            .filter_map(|log| Self::from_log(log.as_ref()))
+            .collect()
+    }
+}
@@ -1,4 +1,7 @@
-use crate::error::NFTStakingContractError;
+use crate::{
+    error::NFTStakingContractError,
+    state::{LOCK_TIERS, MAX_POOL_NAME_LEN, MAX_RARITY_PROOF_LEN, NO_RARITY_TIER, RARITY_TIERS},
+};
 use solana_program::program_error::ProgramError;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -6,14 +9,178 @@ pub struct PlatformData {
     pub percent: u8,
     pub distribution: u64,
     pub accumulation: u64,
+    pub pool_name: [u8; MAX_POOL_NAME_LEN],
+    pub reward_mint_cap: u64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RarityProofData {
+    pub points: u64,
+    pub tier: u8,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct StakeData {
+    pub lock_duration_in_sec: u64,
+    pub rarity_proof: Option<RarityProofData>,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct UnstakeData {
+    pub break_lock: bool,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct LockTiersData {
+    pub durations_in_sec: [u64; LOCK_TIERS],
+    pub multipliers: [u16; LOCK_TIERS],
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct UnstakeCooldownData {
+    pub cooldown_in_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct EarlyExitPenaltyData {
+    pub percentage: u8,
+    pub to_treasury: bool,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WarmUpPeriodData {
+    pub warm_up_period_in_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct LoyaltyCurveData {
+    pub step_in_sec: u64,
+    pub step_percentage: u16,
+    pub max_percentage: u16,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SetBonusData {
+    pub multiplier: u16,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MintPointsOverrideData {
+    pub points: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MintListData {
+    pub list: u8,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct RarityMerkleRootData {
+    pub root: [u8; 32],
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PartnerRewardStreamData {
+    pub emission_per_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PartnerRewardEmissionData {
+    pub stream: u8,
+    pub emission_per_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PartnerRewardStreamIndexData {
+    pub stream: u8,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct RewardMintCapData {
+    pub reward_mint_cap: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ProtocolFeeData {
+    pub fee_bps: u16,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct StakeFeeData {
+    pub lamports: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WithdrawTreasurySolData {
+    pub lamports: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct VestingPeriodData {
+    pub vesting_period_in_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ClaimWindowData {
+    pub claim_window_in_sec: u64,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct DistributeBonusData {
+    pub amount: u64,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+pub struct BoostWindowData {
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub multiplier: u16,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct BoostWindowIndexData {
+    pub window: u8,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum NFTStakingContractInstruction {
     InitializePlatform(PlatformData),
-    StakeNFT,
+    StakeNFT(StakeData),
     UnstakeNFT,
     ClaimReward,
+    SetRewardDestination,
+    SetLockTiers(LockTiersData),
+    CompleteUnstake,
+    SetUnstakeCooldown(UnstakeCooldownData),
+    SetEarlyExitPenalty(EarlyExitPenaltyData),
+    SetWarmUpPeriod(WarmUpPeriodData),
+    SetLoyaltyCurve(LoyaltyCurveData),
+    SetCompletionBonus(SetBonusData),
+    CreateMintPointsOverride(MintPointsOverrideData),
+    UpdateMintPointsOverride(MintPointsOverrideData),
+    RemoveMintPointsOverride,
+    SetMintListMode(MintListData),
+    AddMintListEntry(MintListData),
+    RemoveMintListEntry(MintListData),
+    ForceUnstake,
+    SetRarityMerkleRoot(RarityMerkleRootData),
+    AddPartnerRewardStream(PartnerRewardStreamData),
+    SetPartnerRewardEmission(PartnerRewardEmissionData),
+    SetRewardMintCap(RewardMintCapData),
+    SetProtocolFee(ProtocolFeeData),
+    SetProtocolFeeTreasury,
+    SetStakeFee(StakeFeeData),
+    WithdrawTreasurySol(WithdrawTreasurySolData),
+    SetVestingPeriod(VestingPeriodData),
+    WithdrawVested,
+    SetClaimWindow(ClaimWindowData),
+    ExpireRewards,
+    DistributeBonus(DistributeBonusData),
+    AddBoostWindow(BoostWindowData),
+    RemoveBoostWindow(BoostWindowIndexData),
+    RequestUnstake(UnstakeData),
+    RemovePartnerRewardStream(PartnerRewardStreamIndexData),
 }
 
 impl NFTStakingContractInstruction {
@@ -24,9 +191,43 @@ impl NFTStakingContractInstruction {
 
         Ok(match ins_no {
             0 => Self::InitializePlatform(Self::get_platform_data(data)?),
-            1 => Self::StakeNFT,
+            1 => Self::StakeNFT(Self::get_stake_data(data)?),
             2 => Self::UnstakeNFT,
             3 => Self::ClaimReward,
+            4 => Self::SetRewardDestination,
+            5 => Self::SetLockTiers(Self::get_lock_tiers_data(data)?),
+            6 => Self::CompleteUnstake,
+            7 => Self::SetUnstakeCooldown(Self::get_unstake_cooldown_data(data)?),
+            8 => Self::SetEarlyExitPenalty(Self::get_early_exit_penalty_data(data)?),
+            9 => Self::SetWarmUpPeriod(Self::get_warm_up_period_data(data)?),
+            10 => Self::SetLoyaltyCurve(Self::get_loyalty_curve_data(data)?),
+            11 => Self::SetCompletionBonus(Self::get_set_bonus_data(data)?),
+            12 => Self::CreateMintPointsOverride(Self::get_mint_points_override_data(data)?),
+            13 => Self::UpdateMintPointsOverride(Self::get_mint_points_override_data(data)?),
+            14 => Self::RemoveMintPointsOverride,
+            15 => Self::SetMintListMode(Self::get_mint_list_data(data)?),
+            16 => Self::AddMintListEntry(Self::get_mint_list_data(data)?),
+            17 => Self::RemoveMintListEntry(Self::get_mint_list_data(data)?),
+            18 => Self::ForceUnstake,
+            19 => Self::SetRarityMerkleRoot(Self::get_rarity_merkle_root_data(data)?),
+            20 => Self::AddPartnerRewardStream(Self::get_partner_reward_stream_data(data)?),
+            21 => Self::SetPartnerRewardEmission(Self::get_partner_reward_emission_data(data)?),
+            22 => Self::SetRewardMintCap(Self::get_reward_mint_cap_data(data)?),
+            23 => Self::SetProtocolFee(Self::get_protocol_fee_data(data)?),
+            24 => Self::SetProtocolFeeTreasury,
+            25 => Self::SetStakeFee(Self::get_stake_fee_data(data)?),
+            26 => Self::WithdrawTreasurySol(Self::get_withdraw_treasury_sol_data(data)?),
+            27 => Self::SetVestingPeriod(Self::get_vesting_period_data(data)?),
+            28 => Self::WithdrawVested,
+            29 => Self::SetClaimWindow(Self::get_claim_window_data(data)?),
+            30 => Self::ExpireRewards,
+            31 => Self::DistributeBonus(Self::get_distribute_bonus_data(data)?),
+            32 => Self::AddBoostWindow(Self::get_boost_window_data(data)?),
+            33 => Self::RemoveBoostWindow(Self::get_boost_window_index_data(data)?),
+            34 => Self::RequestUnstake(Self::get_unstake_data(data)?),
+            35 => {
+                Self::RemovePartnerRewardStream(Self::get_partner_reward_stream_index_data(data)?)
+            }
             _ => return Err(NFTStakingContractError::InvalidInstruction.into()),
         })
     }
@@ -48,10 +249,382 @@ impl NFTStakingContractInstruction {
             .map(u64::from_le_bytes)
             .ok_or(NFTStakingContractError::InvalidArgs)?;
 
+        let mut pool_name = [0u8; MAX_POOL_NAME_LEN];
+        let mut reward_mint_cap = 0;
+
+        if let Some(pool_name_len) = data.get(17).map(|len| usize::from(*len)) {
+            let pool_name_bytes = data
+                .get(18..18 + pool_name_len)
+                .filter(|bytes| bytes.len() <= MAX_POOL_NAME_LEN && !bytes.contains(&0))
+                .filter(|bytes| std::str::from_utf8(bytes).is_ok())
+                .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+            pool_name[..pool_name_len].copy_from_slice(pool_name_bytes);
+
+            reward_mint_cap = match data.get(18 + pool_name_len..) {
+                None | Some([]) => 0,
+                Some(slice) => slice
+                    .try_into()
+                    .map(u64::from_le_bytes)
+                    .map_err(|_| NFTStakingContractError::InvalidArgs)?,
+            };
+        }
+
         Ok(PlatformData {
             percent,
             distribution,
             accumulation,
+            pool_name,
+            reward_mint_cap,
+        })
+    }
+
+    fn get_stake_data(data: &[u8]) -> Result<StakeData, ProgramError> {
+        if data.is_empty() {
+            return Ok(StakeData {
+                lock_duration_in_sec: 0,
+                rarity_proof: None,
+            });
+        }
+
+        let lock_duration_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        if data.len() == 8 {
+            return Ok(StakeData {
+                lock_duration_in_sec,
+                rarity_proof: None,
+            });
+        }
+
+        let points = data
+            .get(8..16)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let tier = data
+            .get(16)
+            .copied()
+            .filter(|tier| usize::from(*tier) < RARITY_TIERS || *tier == NO_RARITY_TIER)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let proof_len = data
+            .get(17)
+            .map(|len| usize::from(*len))
+            .filter(|len| *len <= MAX_RARITY_PROOF_LEN)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let proof = data
+            .get(18..18 + proof_len * 32)
+            .ok_or(NFTStakingContractError::InvalidArgs)?
+            .chunks_exact(32)
+            .map(|slice| {
+                slice
+                    .try_into()
+                    .map_err(|_| NFTStakingContractError::InvalidArgs)
+            })
+            .collect::<Result<Vec<[u8; 32]>, _>>()?;
+
+        Ok(StakeData {
+            lock_duration_in_sec,
+            rarity_proof: Some(RarityProofData {
+                points,
+                tier,
+                proof,
+            }),
+        })
+    }
+
+    fn get_unstake_data(data: &[u8]) -> Result<UnstakeData, ProgramError> {
+        let break_lock = match data.first() {
+            None | Some(0) => false,
+            Some(1) => true,
+            _ => return Err(NFTStakingContractError::InvalidArgs.into()),
+        };
+
+        Ok(UnstakeData { break_lock })
+    }
+
+    fn get_lock_tiers_data(data: &[u8]) -> Result<LockTiersData, ProgramError> {
+        let mut durations_in_sec = [0u64; LOCK_TIERS];
+        let mut multipliers = [0u16; LOCK_TIERS];
+
+        for tier in 0..LOCK_TIERS {
+            let offset = tier * 10;
+
+            durations_in_sec[tier] = data
+                .get(offset..offset + 8)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(NFTStakingContractError::InvalidArgs)?;
+            multipliers[tier] = data
+                .get(offset + 8..offset + 10)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u16::from_le_bytes)
+                .ok_or(NFTStakingContractError::InvalidArgs)?;
+        }
+
+        Ok(LockTiersData {
+            durations_in_sec,
+            multipliers,
+        })
+    }
+
+    fn get_unstake_cooldown_data(data: &[u8]) -> Result<UnstakeCooldownData, ProgramError> {
+        let cooldown_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(UnstakeCooldownData { cooldown_in_sec })
+    }
+
+    fn get_early_exit_penalty_data(data: &[u8]) -> Result<EarlyExitPenaltyData, ProgramError> {
+        let percentage = data
+            .get(0..1)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u8::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let to_treasury = match data.get(1) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(NFTStakingContractError::InvalidArgs.into()),
+        };
+
+        Ok(EarlyExitPenaltyData {
+            percentage,
+            to_treasury,
         })
     }
+
+    fn get_warm_up_period_data(data: &[u8]) -> Result<WarmUpPeriodData, ProgramError> {
+        let warm_up_period_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(WarmUpPeriodData {
+            warm_up_period_in_sec,
+        })
+    }
+
+    fn get_loyalty_curve_data(data: &[u8]) -> Result<LoyaltyCurveData, ProgramError> {
+        let step_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let step_percentage = data
+            .get(8..10)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let max_percentage = data
+            .get(10..12)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(LoyaltyCurveData {
+            step_in_sec,
+            step_percentage,
+            max_percentage,
+        })
+    }
+
+    fn get_set_bonus_data(data: &[u8]) -> Result<SetBonusData, ProgramError> {
+        let multiplier = data
+            .get(0..2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(SetBonusData { multiplier })
+    }
+
+    fn get_mint_points_override_data(data: &[u8]) -> Result<MintPointsOverrideData, ProgramError> {
+        let points = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(MintPointsOverrideData { points })
+    }
+
+    fn get_mint_list_data(data: &[u8]) -> Result<MintListData, ProgramError> {
+        let list = data
+            .get(0..1)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u8::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(MintListData { list })
+    }
+
+    fn get_rarity_merkle_root_data(data: &[u8]) -> Result<RarityMerkleRootData, ProgramError> {
+        let root = data
+            .get(0..32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(RarityMerkleRootData { root })
+    }
+
+    fn get_partner_reward_stream_data(
+        data: &[u8],
+    ) -> Result<PartnerRewardStreamData, ProgramError> {
+        let emission_per_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(PartnerRewardStreamData { emission_per_sec })
+    }
+
+    fn get_partner_reward_emission_data(
+        data: &[u8],
+    ) -> Result<PartnerRewardEmissionData, ProgramError> {
+        let stream = data
+            .get(0..1)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u8::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let emission_per_sec = data
+            .get(1..9)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(PartnerRewardEmissionData {
+            stream,
+            emission_per_sec,
+        })
+    }
+
+    fn get_partner_reward_stream_index_data(
+        data: &[u8],
+    ) -> Result<PartnerRewardStreamIndexData, ProgramError> {
+        let stream = data
+            .get(0..1)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u8::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(PartnerRewardStreamIndexData { stream })
+    }
+
+    fn get_reward_mint_cap_data(data: &[u8]) -> Result<RewardMintCapData, ProgramError> {
+        let reward_mint_cap = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(RewardMintCapData { reward_mint_cap })
+    }
+
+    fn get_protocol_fee_data(data: &[u8]) -> Result<ProtocolFeeData, ProgramError> {
+        let fee_bps = data
+            .get(0..2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(ProtocolFeeData { fee_bps })
+    }
+
+    fn get_stake_fee_data(data: &[u8]) -> Result<StakeFeeData, ProgramError> {
+        let lamports = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(StakeFeeData { lamports })
+    }
+
+    fn get_withdraw_treasury_sol_data(
+        data: &[u8],
+    ) -> Result<WithdrawTreasurySolData, ProgramError> {
+        let lamports = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(WithdrawTreasurySolData { lamports })
+    }
+
+    fn get_vesting_period_data(data: &[u8]) -> Result<VestingPeriodData, ProgramError> {
+        let vesting_period_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(VestingPeriodData {
+            vesting_period_in_sec,
+        })
+    }
+
+    fn get_claim_window_data(data: &[u8]) -> Result<ClaimWindowData, ProgramError> {
+        let claim_window_in_sec = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(ClaimWindowData {
+            claim_window_in_sec,
+        })
+    }
+
+    fn get_distribute_bonus_data(data: &[u8]) -> Result<DistributeBonusData, ProgramError> {
+        let amount = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(DistributeBonusData { amount })
+    }
+
+    fn get_boost_window_data(data: &[u8]) -> Result<BoostWindowData, ProgramError> {
+        let start_timestamp = data
+            .get(0..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let end_timestamp = data
+            .get(8..16)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+        let multiplier = data
+            .get(16..18)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(BoostWindowData {
+            start_timestamp,
+            end_timestamp,
+            multiplier,
+        })
+    }
+
+    fn get_boost_window_index_data(data: &[u8]) -> Result<BoostWindowIndexData, ProgramError> {
+        let window = data
+            .get(0..1)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u8::from_le_bytes)
+            .ok_or(NFTStakingContractError::InvalidArgs)?;
+
+        Ok(BoostWindowIndexData { window })
+    }
 }
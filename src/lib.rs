@@ -2,6 +2,7 @@
 pub mod entrypoint;
 
 pub mod error;
+pub mod events;
 pub mod instruction;
 pub mod processor;
 pub mod state;
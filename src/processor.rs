@@ -1,26 +1,75 @@
 use crate::validation::Validator;
 use crate::{
     error::NFTStakingContractError,
-    instruction::{NFTStakingContractInstruction, PlatformData},
-    state::{PlatformState, UserBaseState, UserNFTState},
+    events::{
+        BonusDistributed, BoostWindowAdded, BoostWindowRemoved, ClaimWindowChanged, Claimed,
+        ConfigChanged, EarlyExitPenaltyChanged, EarlyExitPenaltyPaid, ForceUnstaked,
+        LockTiersChanged, LoyaltyCurveChanged, LoyaltyPointsUpdated, MintListEntryChanged,
+        MintListModeChanged, MintPointsOverrideChanged, MintPointsOverrideRemoved,
+        NFTStakingContractEvent, NftLocked, NftPointsActivated, PartnerRewardClaimed,
+        PartnerRewardEmissionChanged, PartnerRewardStreamAdded, PartnerRewardStreamRemoved,
+        PoolCreated, PoolUpdated, ProtocolFeeChanged, ProtocolFeePaid, ProtocolFeeTreasuryChanged,
+        RarityMerkleRootChanged, RewardDestinationChanged, RewardMintCapChanged,
+        RewardMintCapReached, RewardVested, RewardsExpired, SetBonusChanged, SetBonusUpdated,
+        StakeFeeChanged, StakeFeePaid, Staked, TreasurySolWithdrawn, UnstakeCompleted,
+        UnstakeCooldownChanged, UnstakeRequested, Unstaked, UserAccountClosed,
+        VestedRewardWithdrawn, VestingPeriodChanged, WarmUpPeriodChanged,
+    },
+    instruction::{
+        BoostWindowData, BoostWindowIndexData, ClaimWindowData, DistributeBonusData,
+        EarlyExitPenaltyData, LockTiersData, LoyaltyCurveData, MintListData,
+        MintPointsOverrideData, NFTStakingContractInstruction, PartnerRewardEmissionData,
+        PartnerRewardStreamData, PartnerRewardStreamIndexData, PlatformData, ProtocolFeeData,
+        RarityMerkleRootData, RewardMintCapData, SetBonusData, StakeData, StakeFeeData,
+        UnstakeCooldownData, UnstakeData, VestingPeriodData, WarmUpPeriodData,
+        WithdrawTreasurySolData,
+    },
+    state::{
+        MintListEntry, MintPointsOverride, PlatformState, UserBaseState, UserNFTState,
+        VestingSchedule, BPS_DENOMINATOR, DEFAULT_LOCK_TIER_DURATIONS_IN_SEC,
+        DEFAULT_LOCK_TIER_MULTIPLIERS, DEFAULT_LOYALTY_MAX_PERCENTAGE, DEFAULT_LOYALTY_STEP_IN_SEC,
+        DEFAULT_LOYALTY_STEP_PERCENTAGE, DEFAULT_POOL_NAME, DEFAULT_SET_BONUS_MULTIPLIER,
+        DEFAULT_UNSTAKE_COOLDOWN_IN_SEC, DEFAULT_WARM_UP_PERIOD_IN_SEC, MAX_BOOST_MULTIPLIER,
+        MAX_BOOST_WINDOWS, MAX_CLAIM_WINDOW_IN_SEC, MAX_EARLY_EXIT_PENALTY_PERCENTAGE,
+        MAX_PARTNER_REWARD_STREAMS, MAX_PROTOCOL_FEE_BPS, MAX_SET_BONUS_MULTIPLIER,
+        MAX_STAKE_FEE_LAMPORTS, MAX_UNSTAKE_COOLDOWN_IN_SEC, MAX_VESTING_PERIOD_IN_SEC,
+        MAX_WARM_UP_PERIOD_IN_SEC, MINT_LIST_MODE_ALLOWLIST, MINT_LIST_MODE_DENYLIST,
+        MINT_LIST_MODE_OPEN, MIN_CLAIM_WINDOW_IN_SEC, NO_RARITY_TIER, RARITY_CODES, RARITY_POINTS,
+        RARITY_TIERS, REWARD_MODE_MINTED, REWARD_MODE_VAULT,
+    },
 };
 use metaplex_token_metadata::state::Metadata;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    instruction::Instruction,
     msg,
     program::{invoke, invoke_signed},
+    program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
-    system_instruction,
+    system_instruction, system_program,
     sysvar::{clock::Clock, Sysvar},
 };
-use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
 use spl_token;
 
 pub struct Processor;
 
+struct RewardPayoutAccounts<'a, 'b> {
+    reward_mint: &'a AccountInfo<'b>,
+    user_reward_ata: &'a AccountInfo<'b>,
+    pda_reward_token_ata: &'a AccountInfo<'b>,
+    pda_account: &'a AccountInfo<'b>,
+    token_program_account: &'a AccountInfo<'b>,
+    protocol_fee_treasury_ata: Option<&'a AccountInfo<'b>>,
+    vesting_schedule_account: Option<&'a AccountInfo<'b>>,
+    bump_seeds: u8,
+}
+
 impl Processor {
     pub fn unpack_and_process_instruction(
         program_id: &Pubkey,
@@ -33,9 +82,9 @@ impl Processor {
                 Self::process_initialize_paltform(program_id, accounts, platform_data)?;
             }
 
-            NFTStakingContractInstruction::StakeNFT => {
+            NFTStakingContractInstruction::StakeNFT(stake_data) => {
                 msg!("Instruction: StakeNFT");
-                Self::process_stake_nft(program_id, accounts)?;
+                Self::process_stake_nft(program_id, accounts, stake_data)?;
             }
 
             NFTStakingContractInstruction::UnstakeNFT => {
@@ -47,6 +96,170 @@ impl Processor {
                 msg!("Instruction: ClaimReward");
                 Self::process_claim_reward(program_id, accounts)?;
             }
+
+            NFTStakingContractInstruction::SetRewardDestination => {
+                msg!("Instruction: SetRewardDestination");
+                Self::process_set_reward_destination(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetLockTiers(lock_tiers_data) => {
+                msg!("Instruction: SetLockTiers");
+                Self::process_set_lock_tiers(program_id, accounts, lock_tiers_data)?;
+            }
+
+            NFTStakingContractInstruction::CompleteUnstake => {
+                msg!("Instruction: CompleteUnstake");
+                Self::process_complete_unstake(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetUnstakeCooldown(cooldown_data) => {
+                msg!("Instruction: SetUnstakeCooldown");
+                Self::process_set_unstake_cooldown(program_id, accounts, cooldown_data)?;
+            }
+
+            NFTStakingContractInstruction::SetEarlyExitPenalty(penalty_data) => {
+                msg!("Instruction: SetEarlyExitPenalty");
+                Self::process_set_early_exit_penalty(program_id, accounts, penalty_data)?;
+            }
+
+            NFTStakingContractInstruction::SetWarmUpPeriod(warm_up_data) => {
+                msg!("Instruction: SetWarmUpPeriod");
+                Self::process_set_warm_up_period(program_id, accounts, warm_up_data)?;
+            }
+
+            NFTStakingContractInstruction::SetLoyaltyCurve(loyalty_curve_data) => {
+                msg!("Instruction: SetLoyaltyCurve");
+                Self::process_set_loyalty_curve(program_id, accounts, loyalty_curve_data)?;
+            }
+
+            NFTStakingContractInstruction::SetCompletionBonus(set_bonus_data) => {
+                msg!("Instruction: SetCompletionBonus");
+                Self::process_set_completion_bonus(program_id, accounts, set_bonus_data)?;
+            }
+
+            NFTStakingContractInstruction::CreateMintPointsOverride(override_data) => {
+                msg!("Instruction: CreateMintPointsOverride");
+                Self::process_create_mint_points_override(program_id, accounts, override_data)?;
+            }
+
+            NFTStakingContractInstruction::UpdateMintPointsOverride(override_data) => {
+                msg!("Instruction: UpdateMintPointsOverride");
+                Self::process_update_mint_points_override(program_id, accounts, override_data)?;
+            }
+
+            NFTStakingContractInstruction::RemoveMintPointsOverride => {
+                msg!("Instruction: RemoveMintPointsOverride");
+                Self::process_remove_mint_points_override(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetMintListMode(mint_list_data) => {
+                msg!("Instruction: SetMintListMode");
+                Self::process_set_mint_list_mode(program_id, accounts, mint_list_data)?;
+            }
+
+            NFTStakingContractInstruction::AddMintListEntry(mint_list_data) => {
+                msg!("Instruction: AddMintListEntry");
+                Self::process_add_mint_list_entry(program_id, accounts, mint_list_data)?;
+            }
+
+            NFTStakingContractInstruction::RemoveMintListEntry(mint_list_data) => {
+                msg!("Instruction: RemoveMintListEntry");
+                Self::process_remove_mint_list_entry(program_id, accounts, mint_list_data)?;
+            }
+
+            NFTStakingContractInstruction::ForceUnstake => {
+                msg!("Instruction: ForceUnstake");
+                Self::process_force_unstake(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetRarityMerkleRoot(merkle_root_data) => {
+                msg!("Instruction: SetRarityMerkleRoot");
+                Self::process_set_rarity_merkle_root(program_id, accounts, merkle_root_data)?;
+            }
+
+            NFTStakingContractInstruction::AddPartnerRewardStream(stream_data) => {
+                msg!("Instruction: AddPartnerRewardStream");
+                Self::process_add_partner_reward_stream(program_id, accounts, stream_data)?;
+            }
+
+            NFTStakingContractInstruction::SetPartnerRewardEmission(emission_data) => {
+                msg!("Instruction: SetPartnerRewardEmission");
+                Self::process_set_partner_reward_emission(program_id, accounts, emission_data)?;
+            }
+
+            NFTStakingContractInstruction::SetRewardMintCap(mint_cap_data) => {
+                msg!("Instruction: SetRewardMintCap");
+                Self::process_set_reward_mint_cap(program_id, accounts, mint_cap_data)?;
+            }
+
+            NFTStakingContractInstruction::SetProtocolFee(protocol_fee_data) => {
+                msg!("Instruction: SetProtocolFee");
+                Self::process_set_protocol_fee(program_id, accounts, protocol_fee_data)?;
+            }
+
+            NFTStakingContractInstruction::SetProtocolFeeTreasury => {
+                msg!("Instruction: SetProtocolFeeTreasury");
+                Self::process_set_protocol_fee_treasury(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetStakeFee(stake_fee_data) => {
+                msg!("Instruction: SetStakeFee");
+                Self::process_set_stake_fee(program_id, accounts, stake_fee_data)?;
+            }
+
+            NFTStakingContractInstruction::WithdrawTreasurySol(withdraw_data) => {
+                msg!("Instruction: WithdrawTreasurySol");
+                Self::process_withdraw_treasury_sol(program_id, accounts, withdraw_data)?;
+            }
+
+            NFTStakingContractInstruction::SetVestingPeriod(vesting_period_data) => {
+                msg!("Instruction: SetVestingPeriod");
+                Self::process_set_vesting_period(program_id, accounts, vesting_period_data)?;
+            }
+
+            NFTStakingContractInstruction::WithdrawVested => {
+                msg!("Instruction: WithdrawVested");
+                Self::process_withdraw_vested(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::SetClaimWindow(claim_window_data) => {
+                msg!("Instruction: SetClaimWindow");
+                Self::process_set_claim_window(program_id, accounts, claim_window_data)?;
+            }
+
+            NFTStakingContractInstruction::ExpireRewards => {
+                msg!("Instruction: ExpireRewards");
+                Self::process_expire_rewards(program_id, accounts)?;
+            }
+
+            NFTStakingContractInstruction::DistributeBonus(bonus_data) => {
+                msg!("Instruction: DistributeBonus");
+                Self::process_distribute_bonus(program_id, accounts, bonus_data)?;
+            }
+
+            NFTStakingContractInstruction::AddBoostWindow(boost_window_data) => {
+                msg!("Instruction: AddBoostWindow");
+                Self::process_add_boost_window(program_id, accounts, boost_window_data)?;
+            }
+
+            NFTStakingContractInstruction::RemoveBoostWindow(boost_window_index_data) => {
+                msg!("Instruction: RemoveBoostWindow");
+                Self::process_remove_boost_window(program_id, accounts, boost_window_index_data)?;
+            }
+
+            NFTStakingContractInstruction::RequestUnstake(unstake_data) => {
+                msg!("Instruction: RequestUnstake");
+                Self::process_request_unstake(program_id, accounts, unstake_data)?;
+            }
+
+            NFTStakingContractInstruction::RemovePartnerRewardStream(stream_index_data) => {
+                msg!("Instruction: RemovePartnerRewardStream");
+                Self::process_remove_partner_reward_stream(
+                    program_id,
+                    accounts,
+                    stream_index_data,
+                )?;
+            }
         }
 
         Ok(())
@@ -83,19 +296,31 @@ impl Processor {
             program_id,
         );
 
+        let rent = Rent::get()?;
+
         Validator::validate_admin(admin_account)?;
         Validator::validate_token_ata(admin_reward_token_ata, reward_mint)?;
         Validator::validate_token_owner(admin_reward_token_ata, admin_account)?;
         Validator::validate_token_ata(pda_reward_token_ata, reward_mint)?;
         Validator::validate_token_owner(pda_reward_token_ata, pda_account)?;
-        Validator::validate_equality(*pda_account.key, pda)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_token_program(token_program_account)?;
+        Validator::validate_system_program(system_program_account)?;
+
+        let pool_seed = match platform_data.pool_name.iter().position(|byte| *byte == 0) {
+            Some(0) => DEFAULT_POOL_NAME,
+            Some(pool_name_len) => std::str::from_utf8(&platform_data.pool_name[..pool_name_len])
+                .map_err(|_| NFTStakingContractError::InvalidArgs)?,
+            None => std::str::from_utf8(&platform_data.pool_name)
+                .map_err(|_| NFTStakingContractError::InvalidArgs)?,
+        };
 
         let create_program_data_state_ix = system_instruction::create_account_with_seed(
             admin_account.key,
             platform_data_account.key,
             admin_account.key,
-            "NFT Staking Main",
-            Rent::default().minimum_balance(PlatformState::LEN),
+            pool_seed,
+            rent.minimum_balance(PlatformState::LEN),
             PlatformState::LEN as u64,
             program_id,
         );
@@ -109,24 +334,33 @@ impl Processor {
             ],
         )?;
 
-        let transfer_reward_token_to_pda_ata = spl_token::instruction::transfer(
-            &spl_token::id(),
-            admin_reward_token_ata.key,
-            pda_reward_token_ata.key,
-            admin_account.key,
-            &[],
-            100000000000000000,
-        )?;
+        if platform_data.reward_mint_cap > 0 {
+            let reward_mint_unpacked =
+                spl_token::state::Mint::unpack(&reward_mint.try_borrow_data()?)?;
 
-        invoke(
-            &transfer_reward_token_to_pda_ata,
-            &[
-                admin_reward_token_ata.clone(),
-                pda_reward_token_ata.clone(),
-                admin_account.clone(),
-                token_program_account.clone(),
-            ],
-        )?;
+            if reward_mint_unpacked.mint_authority != Some(pda).into() {
+                return Err(NFTStakingContractError::RewardMintAuthorityMismatch.into());
+            }
+        } else {
+            let transfer_reward_token_to_pda_ata = spl_token::instruction::transfer(
+                &spl_token::id(),
+                admin_reward_token_ata.key,
+                pda_reward_token_ata.key,
+                admin_account.key,
+                &[],
+                100000000000000000,
+            )?;
+
+            invoke(
+                &transfer_reward_token_to_pda_ata,
+                &[
+                    admin_reward_token_ata.clone(),
+                    pda_reward_token_ata.clone(),
+                    admin_account.clone(),
+                    token_program_account.clone(),
+                ],
+            )?;
+        }
 
         let mut unpacked_platform_data_account =
             PlatformState::unpack_unchecked(&platform_data_account.try_borrow_data()?)?;
@@ -138,24 +372,62 @@ impl Processor {
         unpacked_platform_data_account.reward_accumulation_in_sec = platform_data.accumulation;
         unpacked_platform_data_account.total_coin_emission = 1000000000u64
             .checked_mul(platform_data.percent.into())
-            .ok_or(NFTStakingContractError::MathError)?
+            .ok_or(NFTStakingContractError::CoinEmissionOverflow)?
             .checked_div(100)
-            .ok_or(NFTStakingContractError::MathError)?;
+            .ok_or(NFTStakingContractError::CoinEmissionOverflow)?;
         unpacked_platform_data_account.reward_mint = *reward_mint.key;
         unpacked_platform_data_account.reward_token_ata = *pda_reward_token_ata.key;
         unpacked_platform_data_account.pda_account = *pda_account.key;
+        unpacked_platform_data_account.pool_name = platform_data.pool_name;
+        unpacked_platform_data_account.lock_tier_durations_in_sec =
+            DEFAULT_LOCK_TIER_DURATIONS_IN_SEC;
+        unpacked_platform_data_account.lock_tier_multipliers = DEFAULT_LOCK_TIER_MULTIPLIERS;
+        unpacked_platform_data_account.unstake_cooldown_in_sec = DEFAULT_UNSTAKE_COOLDOWN_IN_SEC;
+        unpacked_platform_data_account.warm_up_period_in_sec = DEFAULT_WARM_UP_PERIOD_IN_SEC;
+        unpacked_platform_data_account.loyalty_step_in_sec = DEFAULT_LOYALTY_STEP_IN_SEC;
+        unpacked_platform_data_account.loyalty_step_percentage = DEFAULT_LOYALTY_STEP_PERCENTAGE;
+        unpacked_platform_data_account.loyalty_max_percentage = DEFAULT_LOYALTY_MAX_PERCENTAGE;
+        unpacked_platform_data_account.set_bonus_multiplier = DEFAULT_SET_BONUS_MULTIPLIER;
+        unpacked_platform_data_account.reward_mode = if platform_data.reward_mint_cap > 0 {
+            REWARD_MODE_MINTED
+        } else {
+            REWARD_MODE_VAULT
+        };
+        unpacked_platform_data_account.reward_mint_cap = platform_data.reward_mint_cap;
 
         PlatformState::pack(
             unpacked_platform_data_account,
             &mut platform_data_account.try_borrow_mut_data()?,
         )?;
 
-        msg!("Platform Data: {:?}", unpacked_platform_data_account);
+        NFTStakingContractEvent::ConfigChanged(ConfigChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            coin_emission_percentage: unpacked_platform_data_account.coin_emission_percentage,
+            coin_emission_distribution_in_sec: unpacked_platform_data_account
+                .coin_emission_distribution_in_sec,
+            reward_accumulation_in_sec: unpacked_platform_data_account.reward_accumulation_in_sec,
+            total_coin_emission: unpacked_platform_data_account.total_coin_emission,
+        })
+        .emit()?;
+
+        NFTStakingContractEvent::PoolCreated(PoolCreated {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            pool_name: unpacked_platform_data_account.pool_name,
+            reward_mode: unpacked_platform_data_account.reward_mode,
+            reward_mint_cap: unpacked_platform_data_account.reward_mint_cap,
+        })
+        .emit()?;
 
         Ok(())
     }
 
-    fn process_stake_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_stake_nft(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        stake_data: StakeData,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         let user_account = next_account_info(account_info_iter)?;
@@ -184,6 +456,10 @@ impl Processor {
 
         let metadata_program_account = next_account_info(account_info_iter)?;
 
+        let mint_points_override_account = next_account_info(account_info_iter)?;
+
+        let mint_list_entry_account = next_account_info(account_info_iter)?;
+
         let token_program_account = next_account_info(account_info_iter)?;
 
         let rent_sysvar_account = next_account_info(account_info_iter)?;
@@ -200,39 +476,72 @@ impl Processor {
             program_id,
         );
 
+        let rent = Rent::get()?;
+
         Validator::validate_admin(admin_account)?;
         Validator::validate_is_signer(user_account)?;
-        Validator::validate_equality(*pda_account.key, pda)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
         Validator::validate_nft_ata(user_nft_ata, user_nft_mint)?;
         Validator::validate_token_owner(user_nft_ata, user_account)?;
         Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_rent_exempt(platform_data_account, &rent)?;
+        Validator::validate_metadata_program(metadata_program_account)?;
+        Validator::validate_token_program(token_program_account)?;
+        Validator::validate_rent_sysvar(rent_sysvar_account)?;
+        Validator::validate_associated_token_program(associated_token_account_program_account)?;
+        Validator::validate_system_program(system_program_account)?;
 
         Self::update_pool(platform_data_account)?;
 
+        let clock = Clock::get()?;
+
+        let canonical_user_reward_ata =
+            get_associated_token_address(user_account.key, reward_mint.key);
+
+        let (user_base_state_key, user_base_state_bump) =
+            Self::find_user_base_state_address(program_id, platform_data_account, user_account);
+
+        Validator::validate_equality(
+            *user_base_state_account.key,
+            user_base_state_key,
+            NFTStakingContractError::UserStateAddressMismatch,
+        )?;
+
         if user_base_state_account.data_is_empty() {
-            let create_user_base_state_ix = system_instruction::create_account_with_seed(
+            Validator::validate_equality(
+                *user_reward_ata.key,
+                canonical_user_reward_ata,
+                NFTStakingContractError::RewardAtaNotCanonical,
+            )?;
+
+            let create_user_base_state_ix = system_instruction::create_account(
                 user_account.key,
                 user_base_state_account.key,
-                user_account.key,
-                "NFT Staking User Base",
-                Rent::default().minimum_balance(UserBaseState::LEN),
+                rent.minimum_balance(UserBaseState::LEN),
                 UserBaseState::LEN as u64,
                 program_id,
             );
 
-            invoke(
+            invoke_signed(
                 &create_user_base_state_ix,
                 &[
                     user_account.clone(),
                     user_base_state_account.clone(),
                     system_program_account.clone(),
                 ],
+                &[&[
+                    "user_base_state".as_bytes(),
+                    platform_data_account.key.as_ref(),
+                    user_account.key.as_ref(),
+                    &[user_base_state_bump],
+                ]],
             )?;
 
             let mut unpacked_user_base_state_account =
                 UserBaseState::unpack_unchecked(&user_base_state_account.try_borrow_data()?)?;
 
             unpacked_user_base_state_account.is_initialized = true;
+            unpacked_user_base_state_account.platform = *platform_data_account.key;
             unpacked_user_base_state_account.user = *user_account.key;
             unpacked_user_base_state_account.user_reward_ata = *user_reward_ata.key;
 
@@ -242,39 +551,159 @@ impl Processor {
             )?;
         } else {
             Validator::validate_state_account(user_base_state_account, *program_id)?;
+            Validator::validate_rent_exempt(user_base_state_account, &rent)?;
         }
 
         let mut unpacked_platform_data_account =
             PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
 
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_token_ata,
+            *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        if unpacked_platform_data_account.mint_list_mode != MINT_LIST_MODE_OPEN {
+            let (mint_list_entry_key, _mint_list_entry_bump) = Self::find_mint_list_entry_address(
+                program_id,
+                platform_data_account,
+                user_nft_mint,
+                unpacked_platform_data_account.mint_list_mode,
+            );
+
+            Validator::validate_equality(
+                *mint_list_entry_account.key,
+                mint_list_entry_key,
+                NFTStakingContractError::MintListEntryMismatch,
+            )?;
+
+            let listed = !mint_list_entry_account.data_is_empty();
+
+            if listed {
+                Validator::validate_state_account(mint_list_entry_account, *program_id)?;
+            }
+
+            if unpacked_platform_data_account.mint_list_mode == MINT_LIST_MODE_ALLOWLIST && !listed
+            {
+                return Err(NFTStakingContractError::MintNotAllowlisted.into());
+            }
+
+            if unpacked_platform_data_account.mint_list_mode == MINT_LIST_MODE_DENYLIST && listed {
+                return Err(NFTStakingContractError::MintDenylisted.into());
+            }
+        }
+
+        if unpacked_platform_data_account.stake_fee_lamports > 0 {
+            let sol_treasury_account = next_account_info(account_info_iter)?;
+
+            let (sol_treasury, _sol_treasury_bump) =
+                Self::find_sol_treasury_address(program_id, platform_data_account);
+
+            Validator::validate_equality(
+                *sol_treasury_account.key,
+                sol_treasury,
+                NFTStakingContractError::SolTreasuryMismatch,
+            )?;
+
+            let transfer_stake_fee_ix = system_instruction::transfer(
+                user_account.key,
+                sol_treasury_account.key,
+                unpacked_platform_data_account.stake_fee_lamports,
+            );
+
+            invoke(
+                &transfer_stake_fee_ix,
+                &[
+                    user_account.clone(),
+                    sol_treasury_account.clone(),
+                    system_program_account.clone(),
+                ],
+            )?;
+
+            NFTStakingContractEvent::StakeFeePaid(StakeFeePaid {
+                user: *user_account.key,
+                sol_treasury: *sol_treasury_account.key,
+                lamports: unpacked_platform_data_account.stake_fee_lamports,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        }
+
+        let lock_tier = unpacked_platform_data_account
+            .lock_tier_durations_in_sec
+            .iter()
+            .position(|duration| *duration == stake_data.lock_duration_in_sec)
+            .ok_or(NFTStakingContractError::InvalidLockDuration)?;
+
         let mut unpacked_user_base_state_account =
-            UserBaseState::unpack_unchecked(&user_base_state_account.try_borrow_data()?)?;
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.platform,
+            *platform_data_account.key,
+            NFTStakingContractError::PlatformMismatch,
+        )?;
+
+        let initial_user_nft_points = unpacked_user_base_state_account.total_nft_points;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user_reward_ata,
+            *user_reward_ata.key,
+            NFTStakingContractError::RewardAtaMismatch,
+        )?;
+
+        let (user_nft_state_key, user_nft_state_bump) =
+            Self::find_user_nft_state_address(program_id, platform_data_account, user_nft_mint);
 
-        let seed = format!("{}", unpacked_platform_data_account.total_staked_nfts);
+        Validator::validate_equality(
+            *user_nft_state_account.key,
+            user_nft_state_key,
+            NFTStakingContractError::UserStateAddressMismatch,
+        )?;
 
-        let create_user_nft_state_ix = system_instruction::create_account_with_seed(
+        let create_user_nft_state_ix = system_instruction::create_account(
             user_account.key,
             user_nft_state_account.key,
-            user_account.key,
-            &seed,
-            Rent::default().minimum_balance(UserNFTState::LEN),
+            rent.minimum_balance(UserNFTState::LEN),
             UserNFTState::LEN as u64,
             program_id,
         );
 
-        invoke(
+        invoke_signed(
             &create_user_nft_state_ix,
             &[
                 user_account.clone(),
                 user_nft_state_account.clone(),
                 system_program_account.clone(),
             ],
+            &[&[
+                "user_nft_state".as_bytes(),
+                platform_data_account.key.as_ref(),
+                user_nft_mint.key.as_ref(),
+                &[user_nft_state_bump],
+            ]],
         )?;
 
         let mut unpacked_user_nft_state_account =
             UserNFTState::unpack_unchecked(&user_nft_state_account.try_borrow_data()?)?;
 
         if user_reward_ata.data_is_empty() {
+            Validator::validate_equality(
+                *user_reward_ata.key,
+                canonical_user_reward_ata,
+                NFTStakingContractError::RewardAtaNotCanonical,
+            )?;
+
             let create_associated_reward_token_account_ix = create_associated_token_account(
                 user_account.key,
                 user_account.key,
@@ -296,6 +725,8 @@ impl Processor {
             )?;
         }
 
+        Validator::validate_token_ata(user_reward_ata, reward_mint)?;
+
         let set_authority_pda_ins = spl_token::instruction::set_authority(
             &spl_token::ID,
             user_nft_ata.key,
@@ -314,71 +745,70 @@ impl Processor {
             ],
         )?;
 
-        msg!(
-            "Reward Debt Before: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+        Self::accrue_partner_rewards(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        let payout_accounts = RewardPayoutAccounts {
+            reward_mint,
+            user_reward_ata,
+            pda_reward_token_ata,
+            pda_account,
+            token_program_account,
+            protocol_fee_treasury_ata: Self::next_protocol_fee_treasury_ata(
+                account_info_iter,
+                &unpacked_platform_data_account,
+            )?,
+            vesting_schedule_account: Self::next_vesting_schedule_account(
+                program_id,
+                account_info_iter,
+                user_account,
+                user_account,
+                platform_data_account,
+                &unpacked_platform_data_account,
+            )?,
+            bump_seeds,
+        };
 
         let dividend = (100_u64)
             .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+        let mut pending_reward = 0;
 
         if unpacked_user_base_state_account.total_staked_nfts > 0 {
-            let pending_reward = unpacked_user_base_state_account
+            pending_reward = unpacked_user_base_state_account
                 .total_staked_nfts
                 .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
                 .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
                 .checked_div(dividend)
-                .ok_or(NFTStakingContractError::MathError)?
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
                 .checked_sub(unpacked_user_base_state_account.reward_debt)
-                .ok_or(NFTStakingContractError::MathError)?;
-
-            msg!("Pending Reward: {}", pending_reward / 100);
-
-            if pending_reward > 0 {
-                let transfer_reward_ix = spl_token::instruction::transfer(
-                    &spl_token::id(),
-                    pda_reward_token_ata.key,
-                    user_reward_ata.key,
-                    pda_account.key,
-                    &[],
-                    pending_reward * 1000000,
-                )?;
-
-                invoke_signed(
-                    &transfer_reward_ix,
-                    &[
-                        pda_reward_token_ata.clone(),
-                        user_reward_ata.clone(),
-                        pda_account.clone(),
-                        token_program_account.clone(),
-                    ],
-                    &[&[
-                        "nft_staking_contract".as_bytes(),
-                        platform_data_account.key.as_ref(),
-                        &[bump_seeds],
-                    ]],
-                )?;
-
-                unpacked_user_base_state_account.total_reward_claimed =
-                    unpacked_user_base_state_account
-                        .total_reward_claimed
-                        .checked_add(pending_reward)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+                .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
         }
 
+        Self::settle_rewards(
+            platform_data_account,
+            &mut unpacked_platform_data_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &payout_accounts,
+            pending_reward,
+            &clock,
+        )?;
+
         unpacked_platform_data_account.total_staked_nfts = unpacked_platform_data_account
             .total_staked_nfts
             .checked_add(1)
-            .ok_or(NFTStakingContractError::MathError)?;
+            .ok_or(NFTStakingContractError::StakedCountOverflow)?;
 
         unpacked_user_base_state_account.total_staked_nfts = unpacked_user_base_state_account
             .total_staked_nfts
             .checked_add(1)
-            .ok_or(NFTStakingContractError::MathError)?;
+            .ok_or(NFTStakingContractError::StakedCountOverflow)?;
 
         unpacked_user_nft_state_account.is_initialized = true;
         unpacked_user_nft_state_account.user = *user_account.key;
@@ -397,78 +827,157 @@ impl Processor {
         let (metadata_key, _metadata_bump_seed) =
             Pubkey::find_program_address(metadata_seeds, metadata_program_account.key);
 
-        if metadata_key != *nft_metadata_account.key {
-            return Err(NFTStakingContractError::InvalidNFTMetadata.into());
+        if metadata_key != *nft_metadata_account.key
+            || *nft_metadata_account.owner != *metadata_program_account.key
+        {
+            return Err(NFTStakingContractError::MetadataAccountMismatch.into());
         }
 
         let nft_metadata = Metadata::from_account_info(nft_metadata_account)?;
 
         let nft_name_split: Vec<&str> = nft_metadata.data.name.split(' ').collect();
 
-        let mut rarity = nft_name_split[1];
+        let rarity = nft_name_split
+            .get(1)
+            .map(|rarity| rarity.trim_matches(char::from(0)));
 
-        rarity = rarity.trim_matches(char::from(0));
+        let rarity_tier =
+            rarity.and_then(|rarity| RARITY_CODES.iter().position(|code| *code == rarity));
 
-        match rarity {
-            "CO" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_add(10)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        let (mint_points_override_key, _mint_points_override_bump) =
+            Self::find_mint_points_override_address(
+                program_id,
+                platform_data_account,
+                user_nft_mint,
+            );
 
-            "RA" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_add(20)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        Validator::validate_equality(
+            *mint_points_override_account.key,
+            mint_points_override_key,
+            NFTStakingContractError::MintPointsOverrideMismatch,
+        )?;
 
-            "EP" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_add(50)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        let (rarity_points, rarity_tier) = if !mint_points_override_account.data_is_empty() {
+            Validator::validate_state_account(mint_points_override_account, *program_id)?;
 
-            "LE" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_add(100)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+            (
+                MintPointsOverride::unpack(&mint_points_override_account.try_borrow_data()?)?
+                    .points,
+                rarity_tier,
+            )
+        } else if unpacked_platform_data_account.rarity_merkle_root != [0; 32] {
+            // The proven leaf carries the rarity tier as well as the points,
+            // so the metadata name is not trusted for the set bonus either.
+            let rarity_proof = stake_data
+                .rarity_proof
+                .as_ref()
+                .ok_or(NFTStakingContractError::MissingRarityProof)?;
+
+            Validator::validate_rarity_proof(
+                &unpacked_platform_data_account.rarity_merkle_root,
+                user_nft_mint.key,
+                rarity_proof.points,
+                rarity_proof.tier,
+                &rarity_proof.proof,
+            )?;
 
-            _ => return Err(NFTStakingContractError::InvalidNFTMetadata.into()),
+            (
+                rarity_proof.points,
+                Some(usize::from(rarity_proof.tier)).filter(|tier| *tier < RARITY_TIERS),
+            )
+        } else if rarity.is_none() {
+            return Err(NFTStakingContractError::MissingRarityCode.into());
+        } else {
+            let rarity_tier = rarity_tier.ok_or(NFTStakingContractError::UnknownRarityCode)?;
+
+            (RARITY_POINTS[rarity_tier], Some(rarity_tier))
+        };
+
+        let nft_points = rarity_points
+            .checked_mul(unpacked_platform_data_account.lock_tier_multipliers[lock_tier].into())
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?
+            .checked_div(100)
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+
+        unpacked_user_nft_state_account.nft_points = nft_points;
+        unpacked_user_nft_state_account.base_nft_points = nft_points;
+        unpacked_user_nft_state_account.rarity_tier =
+            rarity_tier.map_or(NO_RARITY_TIER, |rarity_tier| rarity_tier as u8);
+        unpacked_user_nft_state_account.unlock_timestamp = (clock.unix_timestamp as u64)
+            .checked_add(stake_data.lock_duration_in_sec)
+            .ok_or(NFTStakingContractError::InvalidLockDuration)?;
+
+        unpacked_user_nft_state_account.staked_at = clock.unix_timestamp as u64;
+
+        Self::refresh_nft_points(
+            program_id,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            account_info_iter.as_slice(),
+            Some(user_nft_state_account.key),
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
+
+        if unpacked_platform_data_account.warm_up_period_in_sec == 0 {
+            unpacked_user_base_state_account.total_nft_points = unpacked_user_base_state_account
+                .total_nft_points
+                .checked_add(nft_points)
+                .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+        } else {
+            unpacked_user_base_state_account.pending_nft_points = unpacked_user_base_state_account
+                .pending_nft_points
+                .checked_add(nft_points)
+                .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+
+            unpacked_user_nft_state_account.warm_up_until = (clock.unix_timestamp as u64)
+                .checked_add(unpacked_platform_data_account.warm_up_period_in_sec)
+                .ok_or(NFTStakingContractError::InvalidWarmUpPeriod)?;
         }
 
-        msg!("nft points {}", unpacked_user_base_state_account
-            .total_nft_points);
+        if let Some(rarity_tier) = rarity_tier {
+            unpacked_user_base_state_account.rarity_counts[rarity_tier] =
+                unpacked_user_base_state_account.rarity_counts[rarity_tier]
+                    .checked_add(1)
+                    .ok_or(NFTStakingContractError::StakedCountOverflow)?;
+        }
+
+        Self::apply_set_bonus(
+            user_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
 
         let dividend_after = (100_u64)
             .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
-        
-        msg!("dividend after {}", dividend_after);
+            .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
 
         if dividend_after > 0 {
             unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
                 .total_staked_nfts
                 .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
                 .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
                 .checked_div(dividend_after)
-                .ok_or(NFTStakingContractError::MathError)?;
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
         }
 
-        msg!(
-            "Reward Debt After: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+        Self::reset_partner_reward_debts(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        unpacked_user_base_state_account.expiry_checkpoint_reward = 0;
+        unpacked_user_base_state_account.expiry_checkpoint_timestamp = clock.unix_timestamp as u64;
+
+        Self::sync_platform_nft_points(
+            &mut unpacked_platform_data_account,
+            &unpacked_user_base_state_account,
+            initial_user_nft_points,
+        )?;
 
         PlatformState::pack(
             unpacked_platform_data_account,
@@ -485,9 +994,29 @@ impl Processor {
             &mut user_nft_state_account.try_borrow_mut_data()?,
         )?;
 
-        msg!("Platform Data: {:?}", unpacked_platform_data_account);
-        msg!("User Base: {:?}", unpacked_user_base_state_account);
-        msg!("User NFT: {:?}", unpacked_user_nft_state_account);
+        NFTStakingContractEvent::Staked(Staked {
+            user: *user_account.key,
+            user_base_state: *user_base_state_account.key,
+            user_nft_state: *user_nft_state_account.key,
+            nft_mint: *user_nft_mint.key,
+            nft_points,
+            user_total_staked_nfts: unpacked_user_base_state_account.total_staked_nfts,
+            user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+            platform_total_staked_nfts: unpacked_platform_data_account.total_staked_nfts,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        if stake_data.lock_duration_in_sec > 0 {
+            NFTStakingContractEvent::NftLocked(NftLocked {
+                user: *user_account.key,
+                user_nft_state: *user_nft_state_account.key,
+                nft_mint: *user_nft_mint.key,
+                unlock_timestamp: unpacked_user_nft_state_account.unlock_timestamp,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        }
 
         Ok(())
     }
@@ -521,56 +1050,585 @@ impl Processor {
 
         let token_program_account = next_account_info(account_info_iter)?;
 
-        let (pda, bump_seeds) = Pubkey::find_program_address(
-            &[
-                "nft_staking_contract".as_bytes(),
-                platform_data_account.key.as_ref(),
-            ],
-            program_id,
-        );
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_state_account(user_nft_state_account, *program_id)?;
+        Validator::validate_metadata_program(metadata_program_account)?;
 
-        Validator::validate_is_signer(user_account)?;
-        Validator::validate_equality(*pda_account.key, pda)?;
-        Validator::validate_nft_ata(user_nft_ata, user_nft_mint)?;
+        const METADATA_PREFIX: &str = "metadata";
+
+        let metadata_seeds = &[
+            METADATA_PREFIX.as_bytes(),
+            metadata_program_account.key.as_ref(),
+            user_nft_mint.key.as_ref(),
+        ];
+
+        let (metadata_key, _metadata_bump_seed) =
+            Pubkey::find_program_address(metadata_seeds, metadata_program_account.key);
+
+        if metadata_key != *nft_metadata_account.key
+            || *nft_metadata_account.owner != *metadata_program_account.key
+        {
+            return Err(NFTStakingContractError::MetadataAccountMismatch.into());
+        }
+
+        let unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        // UnstakeNFT predates the unbonding cooldown, so it is only available
+        // without one and then requests and completes the unstake at once.
+        if unpacked_platform_data_account.unstake_cooldown_in_sec > 0 {
+            return Err(NFTStakingContractError::UnstakeCooldownEnabled.into());
+        }
+
+        let nft_points =
+            UserNFTState::unpack(&user_nft_state_account.try_borrow_data()?)?.nft_points;
+
+        let request_unstake_accounts: Vec<AccountInfo> = [
+            user_account,
+            platform_data_account,
+            user_base_state_account,
+            user_nft_state_account,
+            user_nft_ata,
+            user_nft_mint,
+            reward_mint,
+            user_reward_ata,
+            pda_reward_token_ata,
+            pda_account,
+            token_program_account,
+        ]
+        .into_iter()
+        .chain(account_info_iter)
+        .cloned()
+        .collect();
+
+        Self::process_request_unstake(
+            program_id,
+            &request_unstake_accounts,
+            UnstakeData { break_lock: false },
+        )?;
+
+        let clock = Clock::get()?;
+
+        let unpacked_user_base_state_account =
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+        let unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        NFTStakingContractEvent::Unstaked(Unstaked {
+            user: *user_account.key,
+            user_base_state: *user_base_state_account.key,
+            user_nft_state: *user_nft_state_account.key,
+            nft_mint: *user_nft_mint.key,
+            nft_points,
+            user_total_staked_nfts: unpacked_user_base_state_account.total_staked_nfts,
+            user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+            platform_total_staked_nfts: unpacked_platform_data_account.total_staked_nfts,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        let complete_unstake_accounts = [
+            user_account.clone(),
+            platform_data_account.clone(),
+            user_base_state_account.clone(),
+            user_nft_state_account.clone(),
+            user_nft_ata.clone(),
+            user_nft_mint.clone(),
+            pda_account.clone(),
+            token_program_account.clone(),
+        ];
+
+        Self::process_complete_unstake(program_id, &complete_unstake_accounts)?;
+
+        Ok(())
+    }
+
+    // Unlike UnstakeNFT, RequestUnstake takes no metadata accounts: the NFT
+    // points are read back from the user NFT state recorded at stake time.
+    fn process_request_unstake(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        unstake_data: UnstakeData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let user_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_ata = next_account_info(account_info_iter)?;
+
+        let user_nft_mint = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let user_reward_ata = next_account_info(account_info_iter)?;
+
+        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        let (pda, bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let rent = Rent::get()?;
+
+        Validator::validate_is_signer(user_account)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_nft_ata(user_nft_ata, user_nft_mint)?;
         Validator::validate_token_owner(user_nft_ata, pda_account)?;
         Validator::validate_token_ata(user_reward_ata, reward_mint)?;
-        Validator::validate_token_owner(user_reward_ata, user_account)?;
         Validator::validate_token_ata(pda_reward_token_ata, reward_mint)?;
         Validator::validate_token_owner(pda_reward_token_ata, pda_account)?;
         Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_rent_exempt(platform_data_account, &rent)?;
         Validator::validate_state_account(user_base_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_base_state_account, &rent)?;
         Validator::validate_state_account(user_nft_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_nft_state_account, &rent)?;
+        Validator::validate_token_program(token_program_account)?;
 
         Self::update_pool(platform_data_account)?;
 
+        let clock = Clock::get()?;
+
         let mut unpacked_user_base_state_account =
             UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
 
-        Validator::validate_equality(unpacked_user_base_state_account.user, *user_account.key)?;
+        Validator::validate_equality(
+            unpacked_user_base_state_account.platform,
+            *platform_data_account.key,
+            NFTStakingContractError::PlatformMismatch,
+        )?;
+
+        let initial_user_nft_points = unpacked_user_base_state_account.total_nft_points;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
         Validator::validate_equality(
             unpacked_user_base_state_account.user_reward_ata,
             *user_reward_ata.key,
+            NFTStakingContractError::RewardAtaMismatch,
         )?;
 
-        let unpacked_user_nft_state_account =
+        let mut unpacked_user_nft_state_account =
             UserNFTState::unpack(&user_nft_state_account.try_borrow_data()?)?;
 
-        Validator::validate_equality(unpacked_user_nft_state_account.user, *user_account.key)?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
         Validator::validate_equality(
             unpacked_user_nft_state_account.user_base_state,
             *user_base_state_account.key,
+            NFTStakingContractError::UserBaseStateMismatch,
         )?;
-        Validator::validate_equality(unpacked_user_nft_state_account.nft_ata, *user_nft_ata.key)?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.nft_ata,
+            *user_nft_ata.key,
+            NFTStakingContractError::NftAtaMismatch,
+        )?;
+
+        if unpacked_user_nft_state_account.cooldown_end_timestamp != 0 {
+            return Err(NFTStakingContractError::UnstakeAlreadyRequested.into());
+        }
 
         let mut unpacked_platform_data_account =
             PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
 
-        Validator::validate_equality(unpacked_platform_data_account.reward_mint, *reward_mint.key)?;
+        let breaking_lock =
+            (clock.unix_timestamp as u64) < unpacked_user_nft_state_account.unlock_timestamp;
+
+        if breaking_lock {
+            if !unstake_data.break_lock {
+                return Err(NFTStakingContractError::NftLocked.into());
+            }
+            if unpacked_platform_data_account.early_exit_penalty_percentage == 0 {
+                return Err(NFTStakingContractError::EarlyExitDisabled.into());
+            }
+        }
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
         Validator::validate_equality(
             unpacked_platform_data_account.reward_token_ata,
             *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        Self::accrue_partner_rewards(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        let payout_accounts = RewardPayoutAccounts {
+            reward_mint,
+            user_reward_ata,
+            pda_reward_token_ata,
+            pda_account,
+            token_program_account,
+            protocol_fee_treasury_ata: Self::next_protocol_fee_treasury_ata(
+                account_info_iter,
+                &unpacked_platform_data_account,
+            )?,
+            vesting_schedule_account: Self::next_vesting_schedule_account(
+                program_id,
+                account_info_iter,
+                user_account,
+                user_account,
+                platform_data_account,
+                &unpacked_platform_data_account,
+            )?,
+            bump_seeds,
+        };
+
+        let penalty_treasury_ata = if unstake_data.break_lock
+            && unpacked_platform_data_account.early_exit_penalty_to_treasury
+        {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            None
+        };
+
+        let dividend = (100_u64)
+            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+        let mut pending_reward = 0;
+        let mut forfeited_reward = 0;
+
+        if unpacked_user_base_state_account.total_staked_nfts > 0 {
+            pending_reward = unpacked_user_base_state_account
+                .total_staked_nfts
+                .checked_mul(unpacked_platform_data_account.reward_per_share)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_div(dividend)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_sub(unpacked_user_base_state_account.reward_debt)
+                .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
+
+            // The penalty only applies to rewards accrued since the last
+            // payout; rewards the user already claimed are not clawed back.
+            if breaking_lock {
+                forfeited_reward = pending_reward
+                    .checked_mul(
+                        unpacked_platform_data_account
+                            .early_exit_penalty_percentage
+                            .into(),
+                    )
+                    .ok_or(NFTStakingContractError::EarlyExitPenaltyOverflow)?
+                    .checked_div(100)
+                    .ok_or(NFTStakingContractError::EarlyExitPenaltyOverflow)?;
+            }
+        }
+
+        Self::settle_rewards(
+            platform_data_account,
+            &mut unpacked_platform_data_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &payout_accounts,
+            pending_reward - forfeited_reward,
+            &clock,
+        )?;
+
+        unpacked_platform_data_account.total_staked_nfts = unpacked_platform_data_account
+            .total_staked_nfts
+            .checked_sub(1)
+            .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+
+        if breaking_lock {
+            if forfeited_reward > 0 {
+                if let Some(penalty_treasury_ata) = penalty_treasury_ata {
+                    Validator::validate_equality(
+                        unpacked_platform_data_account.penalty_treasury_ata,
+                        *penalty_treasury_ata.key,
+                        NFTStakingContractError::PenaltyTreasuryMismatch,
+                    )?;
+
+                    let penalty_amount = forfeited_reward
+                        .checked_mul(1000000)
+                        .ok_or(NFTStakingContractError::ArithmeticOverflow)?;
+
+                    // Nothing is owed to the treasury, so in minted mode it only
+                    // receives what the mint cap still allows.
+                    let minted_penalty_amount = Self::mintable_token_amount(
+                        &unpacked_platform_data_account,
+                        penalty_amount,
+                    );
+
+                    if minted_penalty_amount < penalty_amount {
+                        NFTStakingContractEvent::RewardMintCapReached(RewardMintCapReached {
+                            platform: *platform_data_account.key,
+                            requested_token_amount: penalty_amount,
+                            minted_token_amount: minted_penalty_amount,
+                            total_reward_minted: unpacked_platform_data_account.total_reward_minted,
+                        })
+                        .emit()?;
+                    }
+
+                    if minted_penalty_amount > 0 {
+                        Self::transfer_reward(
+                            platform_data_account,
+                            &mut unpacked_platform_data_account,
+                            &payout_accounts,
+                            penalty_treasury_ata,
+                            minted_penalty_amount,
+                        )?;
+                    }
+                } else {
+                    // The exiting user's own points are left out so the whole
+                    // penalty goes to the other stakers.
+                    let remaining_nft_points = unpacked_platform_data_account
+                        .total_nft_points
+                        .checked_sub(initial_user_nft_points)
+                        .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+
+                    if remaining_nft_points > 0 {
+                        unpacked_platform_data_account.reward_per_share =
+                            unpacked_platform_data_account
+                                .reward_per_share
+                                .checked_add(
+                                    forfeited_reward
+                                        .checked_mul(100)
+                                        .ok_or(NFTStakingContractError::RewardPerShareOverflow)?
+                                        .checked_div(remaining_nft_points)
+                                        .ok_or(NFTStakingContractError::RewardPerShareOverflow)?,
+                                )
+                                .ok_or(NFTStakingContractError::RewardPerShareOverflow)?;
+                    }
+                }
+            }
+
+            NFTStakingContractEvent::EarlyExitPenaltyPaid(EarlyExitPenaltyPaid {
+                user: *user_account.key,
+                user_nft_state: *user_nft_state_account.key,
+                nft_mint: *user_nft_mint.key,
+                pending_reward,
+                forfeited_reward,
+                to_treasury: unpacked_platform_data_account.early_exit_penalty_to_treasury,
+                reward_per_share: unpacked_platform_data_account.reward_per_share,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        }
+
+        Self::refresh_nft_points(
+            program_id,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            account_info_iter.as_slice(),
+            Some(user_nft_state_account.key),
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
+
+        unpacked_user_base_state_account.total_staked_nfts = unpacked_user_base_state_account
+            .total_staked_nfts
+            .checked_sub(1)
+            .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+        unpacked_user_base_state_account.unbonding_nfts = unpacked_user_base_state_account
+            .unbonding_nfts
+            .checked_add(1)
+            .ok_or(NFTStakingContractError::StakedCountOverflow)?;
+
+        let nft_points = unpacked_user_nft_state_account.nft_points;
+
+        if Self::is_warming_up(&unpacked_user_nft_state_account) {
+            unpacked_user_base_state_account.pending_nft_points = unpacked_user_base_state_account
+                .pending_nft_points
+                .checked_sub(nft_points)
+                .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+        } else {
+            unpacked_user_base_state_account.total_nft_points = unpacked_user_base_state_account
+                .total_nft_points
+                .checked_sub(nft_points)
+                .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+        }
+
+        let rarity_tier = usize::from(unpacked_user_nft_state_account.rarity_tier);
+
+        if rarity_tier < RARITY_TIERS {
+            unpacked_user_base_state_account.rarity_counts[rarity_tier] =
+                unpacked_user_base_state_account.rarity_counts[rarity_tier]
+                    .checked_sub(1)
+                    .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+        }
+
+        Self::apply_set_bonus(
+            user_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
+
+        let dividend_after = (100_u64)
+            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+            .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+
+        if dividend_after > 0 {
+            unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
+                .total_staked_nfts
+                .checked_mul(unpacked_platform_data_account.reward_per_share)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                .checked_div(dividend_after)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+        }
+
+        Self::reset_partner_reward_debts(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        unpacked_user_base_state_account.expiry_checkpoint_reward = 0;
+        unpacked_user_base_state_account.expiry_checkpoint_timestamp = clock.unix_timestamp as u64;
+
+        Self::sync_platform_nft_points(
+            &mut unpacked_platform_data_account,
+            &unpacked_user_base_state_account,
+            initial_user_nft_points,
+        )?;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        unpacked_user_nft_state_account.cooldown_end_timestamp = (clock.unix_timestamp as u64)
+            .checked_add(unpacked_platform_data_account.unstake_cooldown_in_sec)
+            .ok_or(NFTStakingContractError::InvalidUnstakeCooldown)?;
+
+        UserNFTState::pack(
+            unpacked_user_nft_state_account,
+            &mut user_nft_state_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::UnstakeRequested(UnstakeRequested {
+            user: *user_account.key,
+            user_base_state: *user_base_state_account.key,
+            user_nft_state: *user_nft_state_account.key,
+            nft_mint: *user_nft_mint.key,
+            nft_points,
+            user_total_staked_nfts: unpacked_user_base_state_account.total_staked_nfts,
+            user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+            platform_total_staked_nfts: unpacked_platform_data_account.total_staked_nfts,
+            cooldown_end_timestamp: unpacked_user_nft_state_account.cooldown_end_timestamp,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        // The base state stays open while the NFT unbonds; CompleteUnstake
+        // closes it after the last one is returned.
+        UserBaseState::pack(
+            unpacked_user_base_state_account,
+            &mut user_base_state_account.try_borrow_mut_data()?,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_complete_unstake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let user_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_ata = next_account_info(account_info_iter)?;
+
+        let user_nft_mint = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        let (pda, bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let rent = Rent::get()?;
+
+        Validator::validate_is_signer(user_account)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_nft_ata(user_nft_ata, user_nft_mint)?;
+        Validator::validate_token_owner(user_nft_ata, pda_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_rent_exempt(platform_data_account, &rent)?;
+        Validator::validate_state_account(user_base_state_account, *program_id)?;
+        Validator::validate_state_account(user_nft_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_nft_state_account, &rent)?;
+        Validator::validate_token_program(token_program_account)?;
+
+        let (user_nft_state_key, _user_nft_state_bump) =
+            Self::find_user_nft_state_address(program_id, platform_data_account, user_nft_mint);
+
+        Validator::validate_equality(
+            *user_nft_state_account.key,
+            user_nft_state_key,
+            NFTStakingContractError::UserStateAddressMismatch,
+        )?;
+
+        let clock = Clock::get()?;
+
+        let unpacked_user_nft_state_account =
+            UserNFTState::unpack(&user_nft_state_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.user_base_state,
+            *user_base_state_account.key,
+            NFTStakingContractError::UserBaseStateMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.nft_ata,
+            *user_nft_ata.key,
+            NFTStakingContractError::NftAtaMismatch,
         )?;
 
+        if unpacked_user_nft_state_account.cooldown_end_timestamp == 0 {
+            return Err(NFTStakingContractError::UnstakeNotRequested.into());
+        }
+
+        if (clock.unix_timestamp as u64) < unpacked_user_nft_state_account.cooldown_end_timestamp {
+            return Err(NFTStakingContractError::UnstakeCooldownActive.into());
+        }
+
         let set_authority_back_to_user_ix = spl_token::instruction::set_authority(
             &spl_token::id(),
             user_nft_ata.key,
@@ -594,59 +1652,209 @@ impl Processor {
             ]],
         )?;
 
-        msg!(
-            "User Staked NFT's Count: {}",
-            unpacked_user_base_state_account.total_staked_nfts
-        );
-        msg!(
-            "Reward Per Share: {}",
-            unpacked_platform_data_account.reward_per_share
-        );
+        Self::close_state_account(user_nft_state_account, user_account)?;
 
+        let mut unpacked_user_base_state_account =
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
 
-        msg!(
-            "Reward Debt Before: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+        unpacked_user_base_state_account.unbonding_nfts = unpacked_user_base_state_account
+            .unbonding_nfts
+            .checked_sub(1)
+            .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+
+        NFTStakingContractEvent::UnstakeCompleted(UnstakeCompleted {
+            user: *user_account.key,
+            user_nft_state: *user_nft_state_account.key,
+            nft_mint: *user_nft_mint.key,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        Self::pack_or_close_user_base_state(
+            user_account,
+            user_base_state_account,
+            unpacked_user_base_state_account,
+            &clock,
+        )?;
 
-        let dividend = (100_u64)
-            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
+        Ok(())
+    }
 
-        msg!(
-            "dividend: {}",
-            dividend
-        );
-        
-        if unpacked_user_base_state_account.total_staked_nfts > 0 {
-            let pending_reward = unpacked_user_base_state_account
-                .total_staked_nfts
-                .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_div(dividend)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_sub(unpacked_user_base_state_account.reward_debt)
-                .ok_or(NFTStakingContractError::MathError)?;
+    fn process_claim_reward(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
-            msg!("Pending Reward: {}", pending_reward / 100);
+        let user_account = next_account_info(account_info_iter)?;
 
-            if pending_reward > 0 {
-                let transfer_reward_ix = spl_token::instruction::transfer(
-                    &spl_token::id(),
-                    pda_reward_token_ata.key,
-                    user_reward_ata.key,
-                    pda_account.key,
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let user_reward_ata = next_account_info(account_info_iter)?;
+
+        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        let (pda, bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let rent = Rent::get()?;
+
+        Validator::validate_is_signer(user_account)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_token_ata(user_reward_ata, reward_mint)?;
+        Validator::validate_token_ata(pda_reward_token_ata, reward_mint)?;
+        Validator::validate_token_owner(pda_reward_token_ata, pda_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_rent_exempt(platform_data_account, &rent)?;
+        Validator::validate_state_account(user_base_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_base_state_account, &rent)?;
+        Validator::validate_token_program(token_program_account)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let clock = Clock::get()?;
+
+        let mut unpacked_user_base_state_account =
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.platform,
+            *platform_data_account.key,
+            NFTStakingContractError::PlatformMismatch,
+        )?;
+
+        let initial_user_nft_points = unpacked_user_base_state_account.total_nft_points;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user_reward_ata,
+            *user_reward_ata.key,
+            NFTStakingContractError::RewardAtaMismatch,
+        )?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_token_ata,
+            *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        Self::accrue_partner_rewards(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        let payout_accounts = RewardPayoutAccounts {
+            reward_mint,
+            user_reward_ata,
+            pda_reward_token_ata,
+            pda_account,
+            token_program_account,
+            protocol_fee_treasury_ata: Self::next_protocol_fee_treasury_ata(
+                account_info_iter,
+                &unpacked_platform_data_account,
+            )?,
+            vesting_schedule_account: Self::next_vesting_schedule_account(
+                program_id,
+                account_info_iter,
+                user_account,
+                user_account,
+                platform_data_account,
+                &unpacked_platform_data_account,
+            )?,
+            bump_seeds,
+        };
+
+        let dividend = (100_u64)
+            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+        let mut pending_reward = 0;
+
+        if unpacked_user_base_state_account.total_staked_nfts > 0 {
+            pending_reward = unpacked_user_base_state_account
+                .total_staked_nfts
+                .checked_mul(unpacked_platform_data_account.reward_per_share)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_div(dividend)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                .checked_sub(unpacked_user_base_state_account.reward_debt)
+                .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
+        }
+
+        Self::settle_rewards(
+            platform_data_account,
+            &mut unpacked_platform_data_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &payout_accounts,
+            pending_reward,
+            &clock,
+        )?;
+
+        for stream in 0..usize::from(unpacked_platform_data_account.partner_stream_count) {
+            if unpacked_platform_data_account.partner_stream_mints[stream] == Pubkey::default() {
+                continue;
+            }
+
+            let partner_vault = next_account_info(account_info_iter)?;
+
+            let user_partner_token_account = next_account_info(account_info_iter)?;
+
+            Validator::validate_equality(
+                *partner_vault.key,
+                unpacked_platform_data_account.partner_stream_vaults[stream],
+                NFTStakingContractError::PartnerRewardVaultMismatch,
+            )?;
+            Validator::validate_token_account(
+                user_partner_token_account,
+                &unpacked_platform_data_account.partner_stream_mints[stream],
+                user_account.key,
+            )?;
+
+            // An underfunded partner vault pays what it holds and the rest
+            // stays owed, so it never blocks the primary reward claim.
+            let partner_reward = unpacked_user_base_state_account.partner_stream_owed[stream]
+                .min(spl_token::state::Account::unpack(&partner_vault.try_borrow_data()?)?.amount);
+
+            if partner_reward > 0 {
+                let transfer_partner_reward_ix = spl_token::instruction::transfer(
+                    &spl_token::id(),
+                    partner_vault.key,
+                    user_partner_token_account.key,
+                    pda_account.key,
                     &[],
-                    pending_reward * 1000000,
+                    partner_reward,
                 )?;
 
                 invoke_signed(
-                    &transfer_reward_ix,
+                    &transfer_partner_reward_ix,
                     &[
-                        pda_reward_token_ata.clone(),
-                        user_reward_ata.clone(),
+                        partner_vault.clone(),
+                        user_partner_token_account.clone(),
                         pda_account.clone(),
                         token_program_account.clone(),
                     ],
@@ -657,300 +1865,3055 @@ impl Processor {
                     ]],
                 )?;
 
-                unpacked_user_base_state_account.total_reward_claimed =
-                    unpacked_user_base_state_account
-                        .total_reward_claimed
-                        .checked_add(pending_reward)
-                        .ok_or(NFTStakingContractError::MathError)?;
+                unpacked_user_base_state_account.partner_stream_owed[stream] -= partner_reward;
+
+                NFTStakingContractEvent::PartnerRewardClaimed(PartnerRewardClaimed {
+                    user: *user_account.key,
+                    user_base_state: *user_base_state_account.key,
+                    stream: stream as u8,
+                    mint: unpacked_platform_data_account.partner_stream_mints[stream],
+                    user_token_account: *user_partner_token_account.key,
+                    token_amount: partner_reward,
+                    timestamp: clock.unix_timestamp,
+                })
+                .emit()?;
             }
         }
 
-        unpacked_platform_data_account.total_staked_nfts = unpacked_platform_data_account
-            .total_staked_nfts
-            .checked_sub(1)
-            .ok_or(NFTStakingContractError::MathError)?;
+        Self::refresh_nft_points(
+            program_id,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            account_info_iter.as_slice(),
+            None,
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
 
-        unpacked_user_base_state_account.total_staked_nfts = unpacked_user_base_state_account
-            .total_staked_nfts
-            .checked_sub(1)
-            .ok_or(NFTStakingContractError::MathError)?;
+        Self::apply_set_bonus(
+            user_account,
+            user_base_state_account,
+            &mut unpacked_user_base_state_account,
+            &unpacked_platform_data_account,
+            &clock,
+        )?;
 
-        const METADATA_PREFIX: &str = "metadata";
+        let dividend_after = (100_u64)
+            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+            .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
 
-        let metadata_seeds = &[
-            METADATA_PREFIX.as_bytes(),
-            metadata_program_account.key.as_ref(),
-            user_nft_mint.key.as_ref(),
-        ];
+        if dividend_after > 0 {
+            unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
+                .total_staked_nfts
+                .checked_mul(unpacked_platform_data_account.reward_per_share)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                .checked_div(dividend_after)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+        }
 
-        let (metadata_key, _metadata_bump_seed) =
-            Pubkey::find_program_address(metadata_seeds, metadata_program_account.key);
+        Self::reset_partner_reward_debts(
+            &unpacked_platform_data_account,
+            &mut unpacked_user_base_state_account,
+        )?;
+
+        unpacked_user_base_state_account.expiry_checkpoint_reward = 0;
+        unpacked_user_base_state_account.expiry_checkpoint_timestamp = clock.unix_timestamp as u64;
+
+        Self::sync_platform_nft_points(
+            &mut unpacked_platform_data_account,
+            &unpacked_user_base_state_account,
+            initial_user_nft_points,
+        )?;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        Self::pack_or_close_user_base_state(
+            user_account,
+            user_base_state_account,
+            unpacked_user_base_state_account,
+            &clock,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_set_reward_destination(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let user_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let reward_destination_account = next_account_info(account_info_iter)?;
+
+        let rent = Rent::get()?;
+
+        Validator::validate_is_signer(user_account)?;
+        Validator::validate_state_account(user_base_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_base_state_account, &rent)?;
+        Validator::validate_token_ata(reward_destination_account, reward_mint)?;
+
+        let mut unpacked_user_base_state_account =
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_user_base_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
+
+        let old_reward_destination = unpacked_user_base_state_account.user_reward_ata;
+
+        unpacked_user_base_state_account.user_reward_ata = *reward_destination_account.key;
+
+        UserBaseState::pack(
+            unpacked_user_base_state_account,
+            &mut user_base_state_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::RewardDestinationChanged(RewardDestinationChanged {
+            user: *user_account.key,
+            user_base_state: *user_base_state_account.key,
+            old_reward_destination,
+            new_reward_destination: *reward_destination_account.key,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_lock_tiers(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        lock_tiers_data: LockTiersData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_lock_tiers(
+            &lock_tiers_data.durations_in_sec,
+            &lock_tiers_data.multipliers,
+        )?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.lock_tier_durations_in_sec =
+            lock_tiers_data.durations_in_sec;
+        unpacked_platform_data_account.lock_tier_multipliers = lock_tiers_data.multipliers;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::LockTiersChanged(LockTiersChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            durations_in_sec: lock_tiers_data.durations_in_sec,
+            multipliers: lock_tiers_data.multipliers,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_unstake_cooldown(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        cooldown_data: UnstakeCooldownData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if cooldown_data.cooldown_in_sec > MAX_UNSTAKE_COOLDOWN_IN_SEC {
+            return Err(NFTStakingContractError::InvalidUnstakeCooldown.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.unstake_cooldown_in_sec = cooldown_data.cooldown_in_sec;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::UnstakeCooldownChanged(UnstakeCooldownChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            cooldown_in_sec: cooldown_data.cooldown_in_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_early_exit_penalty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        penalty_data: EarlyExitPenaltyData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if penalty_data.percentage > MAX_EARLY_EXIT_PENALTY_PERCENTAGE {
+            return Err(NFTStakingContractError::InvalidEarlyExitPenalty.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if penalty_data.to_treasury {
+            let penalty_treasury_ata = next_account_info(account_info_iter)?;
+
+            let reward_mint = next_account_info(account_info_iter)?;
+
+            Validator::validate_equality(
+                unpacked_platform_data_account.reward_mint,
+                *reward_mint.key,
+                NFTStakingContractError::WrongRewardMint,
+            )?;
+            Validator::validate_token_ata(penalty_treasury_ata, reward_mint)?;
+
+            if *penalty_treasury_ata.key == unpacked_platform_data_account.reward_token_ata {
+                return Err(NFTStakingContractError::InvalidEarlyExitPenalty.into());
+            }
+
+            unpacked_platform_data_account.penalty_treasury_ata = *penalty_treasury_ata.key;
+        } else {
+            unpacked_platform_data_account.penalty_treasury_ata = Pubkey::default();
+        }
+
+        unpacked_platform_data_account.early_exit_penalty_percentage = penalty_data.percentage;
+        unpacked_platform_data_account.early_exit_penalty_to_treasury = penalty_data.to_treasury;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::EarlyExitPenaltyChanged(EarlyExitPenaltyChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            percentage: penalty_data.percentage,
+            to_treasury: penalty_data.to_treasury,
+            penalty_treasury_ata: unpacked_platform_data_account.penalty_treasury_ata,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_warm_up_period(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        warm_up_data: WarmUpPeriodData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if warm_up_data.warm_up_period_in_sec > MAX_WARM_UP_PERIOD_IN_SEC {
+            return Err(NFTStakingContractError::InvalidWarmUpPeriod.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.warm_up_period_in_sec = warm_up_data.warm_up_period_in_sec;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::WarmUpPeriodChanged(WarmUpPeriodChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            warm_up_period_in_sec: warm_up_data.warm_up_period_in_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_loyalty_curve(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        loyalty_curve_data: LoyaltyCurveData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_loyalty_curve(
+            loyalty_curve_data.step_in_sec,
+            loyalty_curve_data.step_percentage,
+            loyalty_curve_data.max_percentage,
+        )?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.loyalty_step_in_sec = loyalty_curve_data.step_in_sec;
+        unpacked_platform_data_account.loyalty_step_percentage = loyalty_curve_data.step_percentage;
+        unpacked_platform_data_account.loyalty_max_percentage = loyalty_curve_data.max_percentage;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::LoyaltyCurveChanged(LoyaltyCurveChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            step_in_sec: loyalty_curve_data.step_in_sec,
+            step_percentage: loyalty_curve_data.step_percentage,
+            max_percentage: loyalty_curve_data.max_percentage,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_completion_bonus(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        set_bonus_data: SetBonusData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if set_bonus_data.multiplier < 100 || set_bonus_data.multiplier > MAX_SET_BONUS_MULTIPLIER {
+            return Err(NFTStakingContractError::InvalidSetBonus.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.set_bonus_multiplier = set_bonus_data.multiplier;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::SetBonusChanged(SetBonusChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            multiplier: set_bonus_data.multiplier,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_create_mint_points_override(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        override_data: MintPointsOverrideData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_points_override_account = next_account_info(account_info_iter)?;
+
+        let system_program_account = next_account_info(account_info_iter)?;
+
+        let (mint_points_override_key, mint_points_override_bump) =
+            Self::find_mint_points_override_address(program_id, platform_data_account, nft_mint);
+
+        let rent = Rent::get()?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_nft_mint(nft_mint)?;
+        Validator::validate_equality(
+            *mint_points_override_account.key,
+            mint_points_override_key,
+            NFTStakingContractError::MintPointsOverrideMismatch,
+        )?;
+        Validator::validate_system_program(system_program_account)?;
+
+        if override_data.points == 0 {
+            return Err(NFTStakingContractError::InvalidMintPointsOverride.into());
+        }
+
+        let create_mint_points_override_ix = system_instruction::create_account(
+            admin_account.key,
+            mint_points_override_account.key,
+            rent.minimum_balance(MintPointsOverride::LEN),
+            MintPointsOverride::LEN as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_mint_points_override_ix,
+            &[
+                admin_account.clone(),
+                mint_points_override_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                "mint_points_override".as_bytes(),
+                platform_data_account.key.as_ref(),
+                nft_mint.key.as_ref(),
+                &[mint_points_override_bump],
+            ]],
+        )?;
+
+        MintPointsOverride::pack(
+            MintPointsOverride {
+                is_initialized: true,
+                platform: *platform_data_account.key,
+                nft_mint: *nft_mint.key,
+                points: override_data.points,
+            },
+            &mut mint_points_override_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::MintPointsOverrideChanged(MintPointsOverrideChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_points_override: *mint_points_override_account.key,
+            nft_mint: *nft_mint.key,
+            points: override_data.points,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_update_mint_points_override(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        override_data: MintPointsOverrideData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_points_override_account = next_account_info(account_info_iter)?;
+
+        let (mint_points_override_key, _mint_points_override_bump) =
+            Self::find_mint_points_override_address(program_id, platform_data_account, nft_mint);
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(
+            *mint_points_override_account.key,
+            mint_points_override_key,
+            NFTStakingContractError::MintPointsOverrideMismatch,
+        )?;
+        Validator::validate_state_account(mint_points_override_account, *program_id)?;
+
+        if override_data.points == 0 {
+            return Err(NFTStakingContractError::InvalidMintPointsOverride.into());
+        }
+
+        let mut unpacked_mint_points_override_account =
+            MintPointsOverride::unpack(&mint_points_override_account.try_borrow_data()?)?;
+
+        unpacked_mint_points_override_account.points = override_data.points;
+
+        MintPointsOverride::pack(
+            unpacked_mint_points_override_account,
+            &mut mint_points_override_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::MintPointsOverrideChanged(MintPointsOverrideChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_points_override: *mint_points_override_account.key,
+            nft_mint: *nft_mint.key,
+            points: override_data.points,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_remove_mint_points_override(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_points_override_account = next_account_info(account_info_iter)?;
+
+        let (mint_points_override_key, _mint_points_override_bump) =
+            Self::find_mint_points_override_address(program_id, platform_data_account, nft_mint);
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(
+            *mint_points_override_account.key,
+            mint_points_override_key,
+            NFTStakingContractError::MintPointsOverrideMismatch,
+        )?;
+        Validator::validate_state_account(mint_points_override_account, *program_id)?;
+
+        Self::close_state_account(mint_points_override_account, admin_account)?;
+
+        NFTStakingContractEvent::MintPointsOverrideRemoved(MintPointsOverrideRemoved {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_points_override: *mint_points_override_account.key,
+            nft_mint: *nft_mint.key,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_mint_list_mode(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint_list_data: MintListData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if mint_list_data.list > MINT_LIST_MODE_DENYLIST {
+            return Err(NFTStakingContractError::InvalidMintList.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.mint_list_mode = mint_list_data.list;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::MintListModeChanged(MintListModeChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_list_mode: mint_list_data.list,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_add_mint_list_entry(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint_list_data: MintListData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_list_entry_account = next_account_info(account_info_iter)?;
+
+        let system_program_account = next_account_info(account_info_iter)?;
+
+        if mint_list_data.list != MINT_LIST_MODE_ALLOWLIST
+            && mint_list_data.list != MINT_LIST_MODE_DENYLIST
+        {
+            return Err(NFTStakingContractError::InvalidMintList.into());
+        }
+
+        let (mint_list_entry_key, mint_list_entry_bump) = Self::find_mint_list_entry_address(
+            program_id,
+            platform_data_account,
+            nft_mint,
+            mint_list_data.list,
+        );
+
+        let rent = Rent::get()?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_nft_mint(nft_mint)?;
+        Validator::validate_equality(
+            *mint_list_entry_account.key,
+            mint_list_entry_key,
+            NFTStakingContractError::MintListEntryMismatch,
+        )?;
+        Validator::validate_system_program(system_program_account)?;
+
+        let create_mint_list_entry_ix = system_instruction::create_account(
+            admin_account.key,
+            mint_list_entry_account.key,
+            rent.minimum_balance(MintListEntry::LEN),
+            MintListEntry::LEN as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_mint_list_entry_ix,
+            &[
+                admin_account.clone(),
+                mint_list_entry_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                "mint_list".as_bytes(),
+                &[mint_list_data.list],
+                platform_data_account.key.as_ref(),
+                nft_mint.key.as_ref(),
+                &[mint_list_entry_bump],
+            ]],
+        )?;
+
+        MintListEntry::pack(
+            MintListEntry {
+                is_initialized: true,
+                platform: *platform_data_account.key,
+                nft_mint: *nft_mint.key,
+                list: mint_list_data.list,
+            },
+            &mut mint_list_entry_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::MintListEntryChanged(MintListEntryChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_list_entry: *mint_list_entry_account.key,
+            nft_mint: *nft_mint.key,
+            list: mint_list_data.list,
+            listed: true,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_remove_mint_list_entry(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint_list_data: MintListData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_list_entry_account = next_account_info(account_info_iter)?;
+
+        let (mint_list_entry_key, _mint_list_entry_bump) = Self::find_mint_list_entry_address(
+            program_id,
+            platform_data_account,
+            nft_mint,
+            mint_list_data.list,
+        );
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(
+            *mint_list_entry_account.key,
+            mint_list_entry_key,
+            NFTStakingContractError::MintListEntryMismatch,
+        )?;
+        Validator::validate_state_account(mint_list_entry_account, *program_id)?;
+
+        Self::close_state_account(mint_list_entry_account, admin_account)?;
+
+        NFTStakingContractEvent::MintListEntryChanged(MintListEntryChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            mint_list_entry: *mint_list_entry_account.key,
+            nft_mint: *nft_mint.key,
+            list: mint_list_data.list,
+            listed: false,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_force_unstake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let user_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_state_account = next_account_info(account_info_iter)?;
+
+        let user_nft_ata = next_account_info(account_info_iter)?;
+
+        let user_nft_mint = next_account_info(account_info_iter)?;
+
+        let mint_list_entry_account = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let user_reward_ata = next_account_info(account_info_iter)?;
+
+        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        let (pda, bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let (mint_list_entry_key, _mint_list_entry_bump) = Self::find_mint_list_entry_address(
+            program_id,
+            platform_data_account,
+            user_nft_mint,
+            MINT_LIST_MODE_DENYLIST,
+        );
+
+        let rent = Rent::get()?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_nft_ata(user_nft_ata, user_nft_mint)?;
+        Validator::validate_token_owner(user_nft_ata, pda_account)?;
+        Validator::validate_token_ata(pda_reward_token_ata, reward_mint)?;
+        Validator::validate_token_owner(pda_reward_token_ata, pda_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_rent_exempt(platform_data_account, &rent)?;
+        Validator::validate_state_account(user_nft_state_account, *program_id)?;
+        Validator::validate_rent_exempt(user_nft_state_account, &rent)?;
+        Validator::validate_equality(
+            *mint_list_entry_account.key,
+            mint_list_entry_key,
+            NFTStakingContractError::MintListEntryMismatch,
+        )?;
+        Validator::validate_token_program(token_program_account)?;
+
+        if mint_list_entry_account.data_is_empty() {
+            return Err(NFTStakingContractError::MintNotDenylisted.into());
+        }
+
+        Validator::validate_state_account(mint_list_entry_account, *program_id)?;
+
+        let (user_nft_state_key, _user_nft_state_bump) =
+            Self::find_user_nft_state_address(program_id, platform_data_account, user_nft_mint);
+
+        Validator::validate_equality(
+            *user_nft_state_account.key,
+            user_nft_state_key,
+            NFTStakingContractError::UserStateAddressMismatch,
+        )?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let clock = Clock::get()?;
+
+        let unpacked_user_nft_state_account =
+            UserNFTState::unpack(&user_nft_state_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.user,
+            *user_account.key,
+            NFTStakingContractError::UserMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.user_base_state,
+            *user_base_state_account.key,
+            NFTStakingContractError::UserBaseStateMismatch,
+        )?;
+        Validator::validate_equality(
+            unpacked_user_nft_state_account.nft_ata,
+            *user_nft_ata.key,
+            NFTStakingContractError::NftAtaMismatch,
+        )?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_token_ata,
+            *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        let nft_points = unpacked_user_nft_state_account.nft_points;
+
+        if unpacked_user_nft_state_account.cooldown_end_timestamp == 0 {
+            Validator::validate_state_account(user_base_state_account, *program_id)?;
+            Validator::validate_rent_exempt(user_base_state_account, &rent)?;
+            Validator::validate_token_ata(user_reward_ata, reward_mint)?;
+
+            let mut unpacked_user_base_state_account =
+                UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+            Validator::validate_equality(
+                unpacked_user_base_state_account.platform,
+                *platform_data_account.key,
+                NFTStakingContractError::PlatformMismatch,
+            )?;
+
+            let initial_user_nft_points = unpacked_user_base_state_account.total_nft_points;
+
+            Validator::validate_equality(
+                unpacked_user_base_state_account.user_reward_ata,
+                *user_reward_ata.key,
+                NFTStakingContractError::RewardAtaMismatch,
+            )?;
+
+            Self::accrue_partner_rewards(
+                &unpacked_platform_data_account,
+                &mut unpacked_user_base_state_account,
+            )?;
+
+            let payout_accounts = RewardPayoutAccounts {
+                reward_mint,
+                user_reward_ata,
+                pda_reward_token_ata,
+                pda_account,
+                token_program_account,
+                protocol_fee_treasury_ata: Self::next_protocol_fee_treasury_ata(
+                    account_info_iter,
+                    &unpacked_platform_data_account,
+                )?,
+                vesting_schedule_account: Self::next_vesting_schedule_account(
+                    program_id,
+                    account_info_iter,
+                    admin_account,
+                    user_account,
+                    platform_data_account,
+                    &unpacked_platform_data_account,
+                )?,
+                bump_seeds,
+            };
+
+            let dividend = (100_u64)
+                .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+            let mut pending_reward = 0;
+
+            if unpacked_user_base_state_account.total_staked_nfts > 0 {
+                pending_reward = unpacked_user_base_state_account
+                    .total_staked_nfts
+                    .checked_mul(unpacked_platform_data_account.reward_per_share)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_div(dividend)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_sub(unpacked_user_base_state_account.reward_debt)
+                    .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
+            }
+
+            Self::settle_rewards(
+                platform_data_account,
+                &mut unpacked_platform_data_account,
+                user_base_state_account,
+                &mut unpacked_user_base_state_account,
+                &payout_accounts,
+                pending_reward,
+                &clock,
+            )?;
+
+            unpacked_platform_data_account.total_staked_nfts = unpacked_platform_data_account
+                .total_staked_nfts
+                .checked_sub(1)
+                .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+
+            unpacked_user_base_state_account.total_staked_nfts = unpacked_user_base_state_account
+                .total_staked_nfts
+                .checked_sub(1)
+                .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+
+            if Self::is_warming_up(&unpacked_user_nft_state_account) {
+                unpacked_user_base_state_account.pending_nft_points =
+                    unpacked_user_base_state_account
+                        .pending_nft_points
+                        .checked_sub(nft_points)
+                        .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+            } else {
+                unpacked_user_base_state_account.total_nft_points =
+                    unpacked_user_base_state_account
+                        .total_nft_points
+                        .checked_sub(nft_points)
+                        .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+            }
+
+            let rarity_tier = usize::from(unpacked_user_nft_state_account.rarity_tier);
+
+            if rarity_tier < RARITY_TIERS {
+                unpacked_user_base_state_account.rarity_counts[rarity_tier] =
+                    unpacked_user_base_state_account.rarity_counts[rarity_tier]
+                        .checked_sub(1)
+                        .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+            }
+
+            Self::apply_set_bonus(
+                user_account,
+                user_base_state_account,
+                &mut unpacked_user_base_state_account,
+                &unpacked_platform_data_account,
+                &clock,
+            )?;
+
+            let dividend_after = (100_u64)
+                .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+                .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+
+            if dividend_after > 0 {
+                unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
+                    .total_staked_nfts
+                    .checked_mul(unpacked_platform_data_account.reward_per_share)
+                    .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                    .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                    .ok_or(NFTStakingContractError::RewardDebtOverflow)?
+                    .checked_div(dividend_after)
+                    .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+            }
+
+            Self::reset_partner_reward_debts(
+                &unpacked_platform_data_account,
+                &mut unpacked_user_base_state_account,
+            )?;
+
+            unpacked_user_base_state_account.expiry_checkpoint_reward = 0;
+            unpacked_user_base_state_account.expiry_checkpoint_timestamp =
+                clock.unix_timestamp as u64;
+
+            Self::sync_platform_nft_points(
+                &mut unpacked_platform_data_account,
+                &unpacked_user_base_state_account,
+                initial_user_nft_points,
+            )?;
+
+            PlatformState::pack(
+                unpacked_platform_data_account,
+                &mut platform_data_account.try_borrow_mut_data()?,
+            )?;
+
+            NFTStakingContractEvent::ForceUnstaked(ForceUnstaked {
+                admin: *admin_account.key,
+                user: *user_account.key,
+                user_base_state: *user_base_state_account.key,
+                user_nft_state: *user_nft_state_account.key,
+                nft_mint: *user_nft_mint.key,
+                nft_points,
+                user_total_staked_nfts: unpacked_user_base_state_account.total_staked_nfts,
+                user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+                platform_total_staked_nfts: unpacked_platform_data_account.total_staked_nfts,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+
+            Self::pack_or_close_user_base_state(
+                user_account,
+                user_base_state_account,
+                unpacked_user_base_state_account,
+                &clock,
+            )?;
+        } else {
+            Validator::validate_state_account(user_base_state_account, *program_id)?;
+
+            let mut unpacked_user_base_state_account =
+                UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+            unpacked_user_base_state_account.unbonding_nfts = unpacked_user_base_state_account
+                .unbonding_nfts
+                .checked_sub(1)
+                .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
+
+            Self::pack_or_close_user_base_state(
+                user_account,
+                user_base_state_account,
+                unpacked_user_base_state_account,
+                &clock,
+            )?;
+        }
+
+        let set_authority_back_to_user_ix = spl_token::instruction::set_authority(
+            &spl_token::id(),
+            user_nft_ata.key,
+            Some(user_account.key),
+            spl_token::instruction::AuthorityType::AccountOwner,
+            pda_account.key,
+            &[],
+        )?;
+
+        invoke_signed(
+            &set_authority_back_to_user_ix,
+            &[
+                user_nft_ata.clone(),
+                pda_account.clone(),
+                token_program_account.clone(),
+            ],
+            &[&[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+                &[bump_seeds],
+            ]],
+        )?;
+
+        Self::close_state_account(user_nft_state_account, user_account)?;
+
+        NFTStakingContractEvent::UnstakeCompleted(UnstakeCompleted {
+            user: *user_account.key,
+            user_nft_state: *user_nft_state_account.key,
+            nft_mint: *user_nft_mint.key,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_rarity_merkle_root(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        merkle_root_data: RarityMerkleRootData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.rarity_merkle_root = merkle_root_data.root;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::RarityMerkleRootChanged(RarityMerkleRootChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            root: merkle_root_data.root,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_add_partner_reward_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        stream_data: PartnerRewardStreamData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let partner_mint = next_account_info(account_info_iter)?;
+
+        let partner_vault = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let (pda, _bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_token_account(partner_vault, partner_mint.key, pda_account.key)?;
+
+        if *partner_mint.owner != spl_token::ID {
+            return Err(NFTStakingContractError::TokenAccountNotOwnedByTokenProgram.into());
+        }
+
+        spl_token::state::Mint::unpack(&partner_mint.try_borrow_data()?)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        let stream_count = usize::from(unpacked_platform_data_account.partner_stream_count);
+
+        // Slots freed by RemovePartnerRewardStream are reused before a new one
+        // is allocated.
+        let stream = unpacked_platform_data_account.partner_stream_mints[..stream_count]
+            .iter()
+            .position(|mint| *mint == Pubkey::default())
+            .unwrap_or(stream_count);
+
+        if stream >= MAX_PARTNER_REWARD_STREAMS {
+            return Err(NFTStakingContractError::PartnerRewardStreamLimitReached.into());
+        }
+
+        if *partner_mint.key == unpacked_platform_data_account.reward_mint
+            || unpacked_platform_data_account.partner_stream_mints[..stream_count]
+                .contains(partner_mint.key)
+        {
+            return Err(NFTStakingContractError::InvalidPartnerRewardStream.into());
+        }
+
+        unpacked_platform_data_account.partner_stream_mints[stream] = *partner_mint.key;
+        unpacked_platform_data_account.partner_stream_vaults[stream] = *partner_vault.key;
+        unpacked_platform_data_account.partner_stream_emission_per_sec[stream] =
+            stream_data.emission_per_sec;
+        unpacked_platform_data_account.partner_stream_reward_per_share[stream] = 0;
+        unpacked_platform_data_account.partner_stream_generations[stream] += 1;
+
+        if stream == stream_count {
+            unpacked_platform_data_account.partner_stream_count += 1;
+        }
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::PartnerRewardStreamAdded(PartnerRewardStreamAdded {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            stream: stream as u8,
+            mint: *partner_mint.key,
+            vault: *partner_vault.key,
+            emission_per_sec: stream_data.emission_per_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_partner_reward_emission(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        emission_data: PartnerRewardEmissionData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if emission_data.stream >= unpacked_platform_data_account.partner_stream_count
+            || unpacked_platform_data_account.partner_stream_mints
+                [usize::from(emission_data.stream)]
+                == Pubkey::default()
+        {
+            return Err(NFTStakingContractError::InvalidPartnerRewardStream.into());
+        }
+
+        unpacked_platform_data_account.partner_stream_emission_per_sec
+            [usize::from(emission_data.stream)] = emission_data.emission_per_sec;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::PartnerRewardEmissionChanged(PartnerRewardEmissionChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            stream: emission_data.stream,
+            emission_per_sec: emission_data.emission_per_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    // Removing a stream stops its emission and frees the slot for a later
+    // AddPartnerRewardStream. Partner rewards still owed for the removed
+    // stream are forfeited when each user next accrues.
+    fn process_remove_partner_reward_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        stream_index_data: PartnerRewardStreamIndexData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        let stream = usize::from(stream_index_data.stream);
+
+        if stream_index_data.stream >= unpacked_platform_data_account.partner_stream_count
+            || unpacked_platform_data_account.partner_stream_mints[stream] == Pubkey::default()
+        {
+            return Err(NFTStakingContractError::InvalidPartnerRewardStream.into());
+        }
+
+        let mint = unpacked_platform_data_account.partner_stream_mints[stream];
+        let vault = unpacked_platform_data_account.partner_stream_vaults[stream];
+
+        unpacked_platform_data_account.partner_stream_mints[stream] = Pubkey::default();
+        unpacked_platform_data_account.partner_stream_vaults[stream] = Pubkey::default();
+        unpacked_platform_data_account.partner_stream_emission_per_sec[stream] = 0;
+        unpacked_platform_data_account.partner_stream_reward_per_share[stream] = 0;
+        unpacked_platform_data_account.partner_stream_generations[stream] += 1;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::PartnerRewardStreamRemoved(PartnerRewardStreamRemoved {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            stream: stream_index_data.stream,
+            mint,
+            vault,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_reward_mint_cap(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint_cap_data: RewardMintCapData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if unpacked_platform_data_account.reward_mode != REWARD_MODE_MINTED {
+            return Err(NFTStakingContractError::MintedRewardsDisabled.into());
+        }
+
+        if mint_cap_data.reward_mint_cap < unpacked_platform_data_account.total_reward_minted {
+            return Err(NFTStakingContractError::InvalidRewardMintCap.into());
+        }
+
+        unpacked_platform_data_account.reward_mint_cap = mint_cap_data.reward_mint_cap;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::RewardMintCapChanged(RewardMintCapChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            reward_mint_cap: mint_cap_data.reward_mint_cap,
+            total_reward_minted: unpacked_platform_data_account.total_reward_minted,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_protocol_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        protocol_fee_data: ProtocolFeeData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if protocol_fee_data.fee_bps > MAX_PROTOCOL_FEE_BPS
+            || (protocol_fee_data.fee_bps > 0
+                && unpacked_platform_data_account.protocol_fee_treasury_ata == Pubkey::default())
+        {
+            return Err(NFTStakingContractError::InvalidProtocolFee.into());
+        }
+
+        // The fee applies to primary reward payouts only; partner stream
+        // rewards are paid in full.
+        unpacked_platform_data_account.protocol_fee_bps = protocol_fee_data.fee_bps;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::ProtocolFeeChanged(ProtocolFeeChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            fee_bps: protocol_fee_data.fee_bps,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_protocol_fee_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let protocol_fee_treasury_ata = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_token_ata(protocol_fee_treasury_ata, reward_mint)?;
+
+        if *protocol_fee_treasury_ata.key == unpacked_platform_data_account.reward_token_ata {
+            return Err(NFTStakingContractError::ProtocolFeeTreasuryMismatch.into());
+        }
+
+        unpacked_platform_data_account.protocol_fee_treasury_ata = *protocol_fee_treasury_ata.key;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::ProtocolFeeTreasuryChanged(ProtocolFeeTreasuryChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            protocol_fee_treasury_ata: *protocol_fee_treasury_ata.key,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_stake_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        stake_fee_data: StakeFeeData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let rent = Rent::get()?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if stake_fee_data.lamports > MAX_STAKE_FEE_LAMPORTS
+            || (stake_fee_data.lamports > 0 && stake_fee_data.lamports < rent.minimum_balance(0))
+        {
+            return Err(NFTStakingContractError::InvalidStakeFee.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.stake_fee_lamports = stake_fee_data.lamports;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::StakeFeeChanged(StakeFeeChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            stake_fee_lamports: stake_fee_data.lamports,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_withdraw_treasury_sol(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        withdraw_data: WithdrawTreasurySolData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let sol_treasury_account = next_account_info(account_info_iter)?;
+
+        let destination_account = next_account_info(account_info_iter)?;
+
+        let system_program_account = next_account_info(account_info_iter)?;
+
+        let (sol_treasury, sol_treasury_bump) =
+            Self::find_sol_treasury_address(program_id, platform_data_account);
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(
+            *sol_treasury_account.key,
+            sol_treasury,
+            NFTStakingContractError::SolTreasuryMismatch,
+        )?;
+        Validator::validate_system_program(system_program_account)?;
+
+        if withdraw_data.lamports > sol_treasury_account.lamports() {
+            return Err(NFTStakingContractError::InsufficientSolTreasuryBalance.into());
+        }
+
+        let withdraw_treasury_sol_ix = system_instruction::transfer(
+            sol_treasury_account.key,
+            destination_account.key,
+            withdraw_data.lamports,
+        );
+
+        invoke_signed(
+            &withdraw_treasury_sol_ix,
+            &[
+                sol_treasury_account.clone(),
+                destination_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                "sol_treasury".as_bytes(),
+                platform_data_account.key.as_ref(),
+                &[sol_treasury_bump],
+            ]],
+        )?;
+
+        NFTStakingContractEvent::TreasurySolWithdrawn(TreasurySolWithdrawn {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            destination: *destination_account.key,
+            lamports: withdraw_data.lamports,
+            remaining_lamports: sol_treasury_account.lamports(),
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_vesting_period(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        vesting_period_data: VestingPeriodData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if vesting_period_data.vesting_period_in_sec > MAX_VESTING_PERIOD_IN_SEC {
+            return Err(NFTStakingContractError::InvalidVestingPeriod.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.vesting_period_in_sec =
+            vesting_period_data.vesting_period_in_sec;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::VestingPeriodChanged(VestingPeriodChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            vesting_period_in_sec: vesting_period_data.vesting_period_in_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_withdraw_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let user_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let user_base_state_account = next_account_info(account_info_iter)?;
+
+        let vesting_schedule_account = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let user_reward_ata = next_account_info(account_info_iter)?;
+
+        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        let (pda, bump_seeds) = Pubkey::find_program_address(
+            &[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let (user_base_state_key, _user_base_state_bump) =
+            Self::find_user_base_state_address(program_id, platform_data_account, user_account);
+
+        let (vesting_schedule_key, _vesting_schedule_bump) =
+            Self::find_vesting_schedule_address(program_id, platform_data_account, user_account);
+
+        Validator::validate_is_signer(user_account)?;
+        Validator::validate_equality(*pda_account.key, pda, NFTStakingContractError::PdaMismatch)?;
+        Validator::validate_token_ata(user_reward_ata, reward_mint)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_equality(
+            *user_base_state_account.key,
+            user_base_state_key,
+            NFTStakingContractError::UserStateAddressMismatch,
+        )?;
+        Validator::validate_state_account(vesting_schedule_account, *program_id)?;
+        Validator::validate_equality(
+            *vesting_schedule_account.key,
+            vesting_schedule_key,
+            NFTStakingContractError::VestingScheduleMismatch,
+        )?;
+        Validator::validate_token_program(token_program_account)?;
+
+        // Vested rewards follow the destination chosen with SetRewardDestination
+        // and fall back to the canonical reward ATA once the user has exited.
+        let reward_destination = if user_base_state_account.data_is_empty() {
+            get_associated_token_address(user_account.key, reward_mint.key)
+        } else {
+            Validator::validate_state_account(user_base_state_account, *program_id)?;
+
+            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?.user_reward_ata
+        };
+
+        Validator::validate_equality(
+            reward_destination,
+            *user_reward_ata.key,
+            NFTStakingContractError::RewardAtaMismatch,
+        )?;
+
+        let unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_token_ata,
+            *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        let mut unpacked_vesting_schedule_account =
+            VestingSchedule::unpack(&vesting_schedule_account.try_borrow_data()?)?;
+
+        let clock = Clock::get()?;
+
+        Self::checkpoint_vesting(
+            &mut unpacked_vesting_schedule_account,
+            clock.unix_timestamp as u64,
+        )?;
+
+        let token_amount = unpacked_vesting_schedule_account.unlocked_amount;
+
+        if token_amount > 0 {
+            // Minted rewards were reserved against the cap when they vested.
+            let transfer_vested_ix = Self::reward_payout_ix(
+                &unpacked_platform_data_account,
+                pda_reward_token_ata,
+                user_reward_ata,
+                pda_account,
+                token_amount,
+            )?;
+
+            invoke_signed(
+                &transfer_vested_ix,
+                &[
+                    reward_mint.clone(),
+                    pda_reward_token_ata.clone(),
+                    user_reward_ata.clone(),
+                    pda_account.clone(),
+                    token_program_account.clone(),
+                ],
+                &[&[
+                    "nft_staking_contract".as_bytes(),
+                    platform_data_account.key.as_ref(),
+                    &[bump_seeds],
+                ]],
+            )?;
+
+            unpacked_vesting_schedule_account.unlocked_amount = 0;
+            unpacked_vesting_schedule_account.total_withdrawn = unpacked_vesting_schedule_account
+                .total_withdrawn
+                .checked_add(token_amount)
+                .ok_or(NFTStakingContractError::VestingOverflow)?;
+        }
+
+        VestingSchedule::pack(
+            unpacked_vesting_schedule_account,
+            &mut vesting_schedule_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::VestedRewardWithdrawn(VestedRewardWithdrawn {
+            user: *user_account.key,
+            vesting_schedule: *vesting_schedule_account.key,
+            user_reward_ata: *user_reward_ata.key,
+            token_amount,
+            locked_amount: unpacked_vesting_schedule_account.locked_amount,
+            total_withdrawn: unpacked_vesting_schedule_account.total_withdrawn,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_claim_window(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        claim_window_data: ClaimWindowData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        if claim_window_data.claim_window_in_sec != 0
+            && (claim_window_data.claim_window_in_sec < MIN_CLAIM_WINDOW_IN_SEC
+                || claim_window_data.claim_window_in_sec > MAX_CLAIM_WINDOW_IN_SEC)
+        {
+            return Err(NFTStakingContractError::InvalidClaimWindow.into());
+        }
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        unpacked_platform_data_account.claim_window_in_sec = claim_window_data.claim_window_in_sec;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::ClaimWindowChanged(ClaimWindowChanged {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            claim_window_in_sec: claim_window_data.claim_window_in_sec,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    // Expiry works on checkpoints rather than per-second accrual: each crank
+    // past the window forfeits what was pending at the previous checkpoint
+    // and checkpoints the rest, so unclaimed rewards survive between one and
+    // two claim windows. Stake, unstake and claim pay out everything pending
+    // and therefore start a fresh checkpoint.
+    fn process_expire_rewards(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let clock = Clock::get()?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if unpacked_platform_data_account.claim_window_in_sec == 0 {
+            return Err(NFTStakingContractError::RewardExpiryDisabled.into());
+        }
+
+        for user_base_state_account in account_info_iter {
+            Validator::validate_state_account(user_base_state_account, *program_id)?;
+
+            let mut unpacked_user_base_state_account =
+                UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+
+            Validator::validate_equality(
+                unpacked_user_base_state_account.platform,
+                *platform_data_account.key,
+                NFTStakingContractError::PlatformMismatch,
+            )?;
+
+            let checkpoint_expires_at = unpacked_user_base_state_account
+                .expiry_checkpoint_timestamp
+                .saturating_add(unpacked_platform_data_account.claim_window_in_sec);
+
+            if (clock.unix_timestamp as u64) < checkpoint_expires_at {
+                continue;
+            }
+
+            let dividend = (100_u64)
+                .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
+                .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+            let mut pending_reward = 0;
+
+            if unpacked_user_base_state_account.total_staked_nfts > 0 {
+                pending_reward = unpacked_user_base_state_account
+                    .total_staked_nfts
+                    .checked_mul(unpacked_platform_data_account.reward_per_share)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_mul(unpacked_user_base_state_account.total_nft_points)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_div(dividend)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+                    .checked_sub(unpacked_user_base_state_account.reward_debt)
+                    .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
+            }
+
+            let expired_reward = unpacked_user_base_state_account
+                .expiry_checkpoint_reward
+                .min(pending_reward);
+
+            if expired_reward > 0 {
+                unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
+                    .reward_debt
+                    .checked_add(expired_reward)
+                    .ok_or(NFTStakingContractError::RewardDebtOverflow)?;
+                unpacked_platform_data_account.recycled_reward = unpacked_platform_data_account
+                    .recycled_reward
+                    .checked_add(expired_reward)
+                    .ok_or(NFTStakingContractError::RewardGeneratedOverflow)?;
+            }
+
+            unpacked_user_base_state_account.expiry_checkpoint_reward =
+                pending_reward - expired_reward;
+            unpacked_user_base_state_account.expiry_checkpoint_timestamp =
+                clock.unix_timestamp as u64;
+
+            UserBaseState::pack(
+                unpacked_user_base_state_account,
+                &mut user_base_state_account.try_borrow_mut_data()?,
+            )?;
+
+            if expired_reward > 0 {
+                NFTStakingContractEvent::RewardsExpired(RewardsExpired {
+                    platform: *platform_data_account.key,
+                    user: unpacked_user_base_state_account.user,
+                    user_base_state: *user_base_state_account.key,
+                    expired_reward,
+                    remaining_reward: unpacked_user_base_state_account.expiry_checkpoint_reward,
+                    recycled_reward: unpacked_platform_data_account.recycled_reward,
+                    timestamp: clock.unix_timestamp,
+                })
+                .emit()?;
+            }
+        }
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        Ok(())
+    }
+
+    fn process_distribute_bonus(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bonus_data: DistributeBonusData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        let reward_mint = next_account_info(account_info_iter)?;
+
+        let admin_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+
+        let token_program_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+        Validator::validate_token_program(token_program_account)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let clock = Clock::get()?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_mint,
+            *reward_mint.key,
+            NFTStakingContractError::WrongRewardMint,
+        )?;
+        Validator::validate_equality(
+            unpacked_platform_data_account.reward_token_ata,
+            *pda_reward_token_ata.key,
+            NFTStakingContractError::RewardVaultMismatch,
+        )?;
+
+        if bonus_data.amount == 0 {
+            return Err(NFTStakingContractError::InvalidArgs.into());
+        }
+
+        if unpacked_platform_data_account.total_nft_points == 0 {
+            return Err(NFTStakingContractError::NoStakedNftPoints.into());
+        }
+
+        // Minted pools only mint rewards as they are paid out, against the
+        // mint cap, so a bonus could promise more than the cap allows.
+        if unpacked_platform_data_account.reward_mode == REWARD_MODE_MINTED {
+            return Err(NFTStakingContractError::BonusRequiresVaultRewards.into());
+        }
+
+        Validator::validate_token_ata(admin_reward_token_ata, reward_mint)?;
+        Validator::validate_token_owner(admin_reward_token_ata, admin_account)?;
+
+        let transfer_bonus_ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            admin_reward_token_ata.key,
+            pda_reward_token_ata.key,
+            admin_account.key,
+            &[],
+            bonus_data
+                .amount
+                .checked_mul(1000000)
+                .ok_or(NFTStakingContractError::BonusOverflow)?,
+        )?;
+
+        invoke(
+            &transfer_bonus_ix,
+            &[
+                admin_reward_token_ata.clone(),
+                pda_reward_token_ata.clone(),
+                admin_account.clone(),
+                token_program_account.clone(),
+            ],
+        )?;
+
+        let reward_per_share_increase = bonus_data
+            .amount
+            .checked_mul(100)
+            .ok_or(NFTStakingContractError::BonusOverflow)?
+            .checked_div(unpacked_platform_data_account.total_nft_points)
+            .ok_or(NFTStakingContractError::BonusOverflow)?;
+
+        // Rounded up like the recycled reward so the dust left for recycling
+        // never exceeds what the reward per share increase leaves unpaid.
+        let distributed_amount = reward_per_share_increase
+            .checked_mul(unpacked_platform_data_account.total_nft_points)
+            .ok_or(NFTStakingContractError::BonusOverflow)?
+            .checked_add(99)
+            .ok_or(NFTStakingContractError::BonusOverflow)?
+            / 100;
+
+        unpacked_platform_data_account.reward_per_share = unpacked_platform_data_account
+            .reward_per_share
+            .checked_add(reward_per_share_increase)
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?;
+        unpacked_platform_data_account.recycled_reward = unpacked_platform_data_account
+            .recycled_reward
+            .checked_add(bonus_data.amount - distributed_amount)
+            .ok_or(NFTStakingContractError::BonusOverflow)?;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::BonusDistributed(BonusDistributed {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            amount: bonus_data.amount,
+            reward_per_share_increase,
+            total_nft_points: unpacked_platform_data_account.total_nft_points,
+            recycled_reward: unpacked_platform_data_account.recycled_reward,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_add_boost_window(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        boost_window_data: BoostWindowData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let clock = Clock::get()?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        if boost_window_data.start_timestamp < clock.unix_timestamp as u64
+            || boost_window_data.end_timestamp <= boost_window_data.start_timestamp
+            || boost_window_data.multiplier <= 100
+            || boost_window_data.multiplier > MAX_BOOST_MULTIPLIER
+        {
+            return Err(NFTStakingContractError::InvalidBoostWindow.into());
+        }
+
+        let window = usize::from(unpacked_platform_data_account.boost_window_count);
+
+        if window >= MAX_BOOST_WINDOWS {
+            return Err(NFTStakingContractError::BoostWindowLimitReached.into());
+        }
+
+        for existing in 0..window {
+            if boost_window_data.start_timestamp
+                < unpacked_platform_data_account.boost_window_ends[existing]
+                && unpacked_platform_data_account.boost_window_starts[existing]
+                    < boost_window_data.end_timestamp
+            {
+                return Err(NFTStakingContractError::BoostWindowOverlap.into());
+            }
+        }
+
+        unpacked_platform_data_account.boost_window_starts[window] =
+            boost_window_data.start_timestamp;
+        unpacked_platform_data_account.boost_window_ends[window] = boost_window_data.end_timestamp;
+        unpacked_platform_data_account.boost_window_multipliers[window] =
+            boost_window_data.multiplier;
+        unpacked_platform_data_account.boost_window_count += 1;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::BoostWindowAdded(BoostWindowAdded {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            window: window as u8,
+            start_timestamp: boost_window_data.start_timestamp,
+            end_timestamp: boost_window_data.end_timestamp,
+            multiplier: boost_window_data.multiplier,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_remove_boost_window(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        boost_window_index_data: BoostWindowIndexData,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_account = next_account_info(account_info_iter)?;
+
+        let platform_data_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_admin(admin_account)?;
+        Validator::validate_state_account(platform_data_account, *program_id)?;
+
+        Self::update_pool(platform_data_account)?;
+
+        let mut unpacked_platform_data_account =
+            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+
+        let window = usize::from(boost_window_index_data.window);
+        let last_window = usize::from(unpacked_platform_data_account.boost_window_count);
+
+        if window >= last_window {
+            return Err(NFTStakingContractError::InvalidBoostWindow.into());
+        }
+
+        let last_window = last_window - 1;
+
+        let start_timestamp = unpacked_platform_data_account.boost_window_starts[window];
+        let end_timestamp = unpacked_platform_data_account.boost_window_ends[window];
+        let multiplier = unpacked_platform_data_account.boost_window_multipliers[window];
+
+        unpacked_platform_data_account.boost_window_starts[window] =
+            unpacked_platform_data_account.boost_window_starts[last_window];
+        unpacked_platform_data_account.boost_window_ends[window] =
+            unpacked_platform_data_account.boost_window_ends[last_window];
+        unpacked_platform_data_account.boost_window_multipliers[window] =
+            unpacked_platform_data_account.boost_window_multipliers[last_window];
+        unpacked_platform_data_account.boost_window_starts[last_window] = 0;
+        unpacked_platform_data_account.boost_window_ends[last_window] = 0;
+        unpacked_platform_data_account.boost_window_multipliers[last_window] = 0;
+        unpacked_platform_data_account.boost_window_count -= 1;
+
+        PlatformState::pack(
+            unpacked_platform_data_account,
+            &mut platform_data_account.try_borrow_mut_data()?,
+        )?;
+
+        NFTStakingContractEvent::BoostWindowRemoved(BoostWindowRemoved {
+            platform: *platform_data_account.key,
+            admin: *admin_account.key,
+            window: boost_window_index_data.window,
+            start_timestamp,
+            end_timestamp,
+            multiplier,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn sync_platform_nft_points(
+        unpacked_platform_data_account: &mut PlatformState,
+        unpacked_user_base_state_account: &UserBaseState,
+        initial_user_nft_points: u64,
+    ) -> ProgramResult {
+        unpacked_platform_data_account.total_nft_points = unpacked_platform_data_account
+            .total_nft_points
+            .checked_add(unpacked_user_base_state_account.total_nft_points)
+            .and_then(|total_nft_points| total_nft_points.checked_sub(initial_user_nft_points))
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+
+        Ok(())
+    }
+
+    fn next_protocol_fee_treasury_ata<'a, 'b>(
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+        unpacked_platform_data_account: &PlatformState,
+    ) -> Result<Option<&'a AccountInfo<'b>>, ProgramError> {
+        if unpacked_platform_data_account.protocol_fee_bps == 0 {
+            return Ok(None);
+        }
+
+        let protocol_fee_treasury_ata = next_account_info(account_info_iter)?;
+
+        Validator::validate_equality(
+            unpacked_platform_data_account.protocol_fee_treasury_ata,
+            *protocol_fee_treasury_ata.key,
+            NFTStakingContractError::ProtocolFeeTreasuryMismatch,
+        )?;
+
+        Ok(Some(protocol_fee_treasury_ata))
+    }
+
+    fn protocol_fee(
+        unpacked_platform_data_account: &PlatformState,
+        token_amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let protocol_fee = u128::from(token_amount)
+            .checked_mul(unpacked_platform_data_account.protocol_fee_bps.into())
+            .ok_or(NFTStakingContractError::ProtocolFeeOverflow)?
+            .checked_div(BPS_DENOMINATOR.into())
+            .ok_or(NFTStakingContractError::ProtocolFeeOverflow)?;
+
+        Ok(
+            u64::try_from(protocol_fee)
+                .map_err(|_| NFTStakingContractError::ProtocolFeeOverflow)?,
+        )
+    }
+
+    fn next_vesting_schedule_account<'a, 'b>(
+        program_id: &Pubkey,
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+        payer_account: &AccountInfo<'b>,
+        user_account: &AccountInfo<'b>,
+        platform_data_account: &AccountInfo<'b>,
+        unpacked_platform_data_account: &PlatformState,
+    ) -> Result<Option<&'a AccountInfo<'b>>, ProgramError> {
+        if unpacked_platform_data_account.vesting_period_in_sec == 0 {
+            return Ok(None);
+        }
+
+        let vesting_schedule_account = next_account_info(account_info_iter)?;
+
+        let system_program_account = next_account_info(account_info_iter)?;
+
+        let (vesting_schedule_key, vesting_schedule_bump) =
+            Self::find_vesting_schedule_address(program_id, platform_data_account, user_account);
+
+        Validator::validate_equality(
+            *vesting_schedule_account.key,
+            vesting_schedule_key,
+            NFTStakingContractError::VestingScheduleMismatch,
+        )?;
+        Validator::validate_system_program(system_program_account)?;
+
+        if vesting_schedule_account.data_is_empty() {
+            let create_vesting_schedule_ix = system_instruction::create_account(
+                payer_account.key,
+                vesting_schedule_account.key,
+                Rent::get()?.minimum_balance(VestingSchedule::LEN),
+                VestingSchedule::LEN as u64,
+                program_id,
+            );
+
+            invoke_signed(
+                &create_vesting_schedule_ix,
+                &[
+                    payer_account.clone(),
+                    vesting_schedule_account.clone(),
+                    system_program_account.clone(),
+                ],
+                &[&[
+                    "vesting_schedule".as_bytes(),
+                    platform_data_account.key.as_ref(),
+                    user_account.key.as_ref(),
+                    &[vesting_schedule_bump],
+                ]],
+            )?;
+
+            VestingSchedule::pack(
+                VestingSchedule {
+                    is_initialized: true,
+                    platform: *platform_data_account.key,
+                    user: *user_account.key,
+                    locked_amount: 0,
+                    unlocked_amount: 0,
+                    total_withdrawn: 0,
+                    start_timestamp: 0,
+                    end_timestamp: 0,
+                },
+                &mut vesting_schedule_account.try_borrow_mut_data()?,
+            )?;
+        }
+
+        Validator::validate_state_account(vesting_schedule_account, *program_id)?;
+
+        Ok(Some(vesting_schedule_account))
+    }
+
+    // Only primary reward payouts go through here and pay the protocol fee.
+    // Partner stream rewards are paid straight from their vaults in
+    // ClaimReward and are exempt from it.
+    fn settle_rewards<'a>(
+        platform_data_account: &AccountInfo<'a>,
+        unpacked_platform_data_account: &mut PlatformState,
+        user_base_state_account: &AccountInfo<'a>,
+        unpacked_user_base_state_account: &mut UserBaseState,
+        payout_accounts: &RewardPayoutAccounts<'_, 'a>,
+        reward: u64,
+        clock: &Clock,
+    ) -> ProgramResult {
+        let requested_reward = reward
+            .checked_add(unpacked_user_base_state_account.reward_owed)
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+        let requested_token_amount = requested_reward
+            .checked_mul(1000000)
+            .ok_or(NFTStakingContractError::ArithmeticOverflow)?;
+
+        // Whatever the mint cap cannot cover stays owed to the user and is
+        // settled first once the admin raises the cap.
+        let reward =
+            Self::mintable_token_amount(unpacked_platform_data_account, requested_token_amount)
+                / 1000000;
+
+        unpacked_user_base_state_account.reward_owed = requested_reward - reward;
+
+        if reward < requested_reward {
+            NFTStakingContractEvent::RewardMintCapReached(RewardMintCapReached {
+                platform: *platform_data_account.key,
+                requested_token_amount,
+                minted_token_amount: reward * 1000000,
+                total_reward_minted: unpacked_platform_data_account.total_reward_minted,
+            })
+            .emit()?;
+        }
+
+        if reward == 0 {
+            return Ok(());
+        }
+
+        let reward_amount = reward * 1000000;
+        let protocol_fee = Self::protocol_fee(unpacked_platform_data_account, reward_amount)?;
+
+        if let Some(protocol_fee_treasury_ata) = payout_accounts.protocol_fee_treasury_ata {
+            Self::transfer_reward(
+                platform_data_account,
+                unpacked_platform_data_account,
+                payout_accounts,
+                protocol_fee_treasury_ata,
+                protocol_fee,
+            )?;
+
+            NFTStakingContractEvent::ProtocolFeePaid(ProtocolFeePaid {
+                user: unpacked_user_base_state_account.user,
+                user_base_state: *user_base_state_account.key,
+                protocol_fee_treasury_ata: *protocol_fee_treasury_ata.key,
+                token_amount: protocol_fee,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        }
+
+        let token_amount = reward_amount - protocol_fee;
+
+        if let Some(vesting_schedule_account) = payout_accounts.vesting_schedule_account {
+            let mut unpacked_vesting_schedule_account =
+                VestingSchedule::unpack(&vesting_schedule_account.try_borrow_data()?)?;
+
+            Self::reserve_reward_mint(unpacked_platform_data_account, token_amount)?;
+
+            Self::add_vesting(
+                &mut unpacked_vesting_schedule_account,
+                token_amount,
+                unpacked_platform_data_account.vesting_period_in_sec,
+                clock.unix_timestamp as u64,
+            )?;
+
+            VestingSchedule::pack(
+                unpacked_vesting_schedule_account,
+                &mut vesting_schedule_account.try_borrow_mut_data()?,
+            )?;
+
+            NFTStakingContractEvent::RewardVested(RewardVested {
+                user: unpacked_user_base_state_account.user,
+                vesting_schedule: *vesting_schedule_account.key,
+                token_amount,
+                locked_amount: unpacked_vesting_schedule_account.locked_amount,
+                unlocked_amount: unpacked_vesting_schedule_account.unlocked_amount,
+                end_timestamp: unpacked_vesting_schedule_account.end_timestamp,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        } else {
+            Self::transfer_reward(
+                platform_data_account,
+                unpacked_platform_data_account,
+                payout_accounts,
+                payout_accounts.user_reward_ata,
+                token_amount,
+            )?;
+        }
+
+        unpacked_user_base_state_account.total_reward_claimed = unpacked_user_base_state_account
+            .total_reward_claimed
+            .checked_add(reward)
+            .ok_or(NFTStakingContractError::RewardClaimedOverflow)?;
+
+        NFTStakingContractEvent::Claimed(Claimed {
+            user: unpacked_user_base_state_account.user,
+            user_base_state: *user_base_state_account.key,
+            user_reward_ata: *payout_accounts.user_reward_ata.key,
+            reward,
+            token_amount,
+            total_reward_claimed: unpacked_user_base_state_account.total_reward_claimed,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn transfer_reward<'a>(
+        platform_data_account: &AccountInfo<'a>,
+        unpacked_platform_data_account: &mut PlatformState,
+        payout_accounts: &RewardPayoutAccounts<'_, 'a>,
+        destination_ata: &AccountInfo<'a>,
+        token_amount: u64,
+    ) -> ProgramResult {
+        Self::reserve_reward_mint(unpacked_platform_data_account, token_amount)?;
+
+        let transfer_reward_ix = Self::reward_payout_ix(
+            unpacked_platform_data_account,
+            payout_accounts.pda_reward_token_ata,
+            destination_ata,
+            payout_accounts.pda_account,
+            token_amount,
+        )?;
+
+        invoke_signed(
+            &transfer_reward_ix,
+            &[
+                payout_accounts.reward_mint.clone(),
+                payout_accounts.pda_reward_token_ata.clone(),
+                destination_ata.clone(),
+                payout_accounts.pda_account.clone(),
+                payout_accounts.token_program_account.clone(),
+            ],
+            &[&[
+                "nft_staking_contract".as_bytes(),
+                platform_data_account.key.as_ref(),
+                &[payout_accounts.bump_seeds],
+            ]],
+        )
+    }
 
-        if metadata_key != *nft_metadata_account.key {
-            return Err(NFTStakingContractError::InvalidNFTMetadata.into());
+    fn reward_payout_ix(
+        unpacked_platform_data_account: &PlatformState,
+        pda_reward_token_ata: &AccountInfo,
+        destination_ata: &AccountInfo,
+        pda_account: &AccountInfo,
+        token_amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        if unpacked_platform_data_account.reward_mode != REWARD_MODE_MINTED {
+            return spl_token::instruction::transfer(
+                &spl_token::id(),
+                pda_reward_token_ata.key,
+                destination_ata.key,
+                pda_account.key,
+                &[],
+                token_amount,
+            );
         }
 
-        let nft_metadata = Metadata::from_account_info(nft_metadata_account)?;
+        spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &unpacked_platform_data_account.reward_mint,
+            destination_ata.key,
+            pda_account.key,
+            &[],
+            token_amount,
+        )
+    }
 
-        let nft_name_split: Vec<&str> = nft_metadata.data.name.split(' ').collect();
+    fn mintable_token_amount(
+        unpacked_platform_data_account: &PlatformState,
+        token_amount: u64,
+    ) -> u64 {
+        if unpacked_platform_data_account.reward_mode != REWARD_MODE_MINTED {
+            return token_amount;
+        }
 
-        let mut rarity = nft_name_split[1];
+        token_amount.min(
+            unpacked_platform_data_account
+                .reward_mint_cap
+                .saturating_sub(unpacked_platform_data_account.total_reward_minted),
+        )
+    }
 
-        rarity = rarity.trim_matches(char::from(0));
+    fn reserve_reward_mint(
+        unpacked_platform_data_account: &mut PlatformState,
+        token_amount: u64,
+    ) -> ProgramResult {
+        if unpacked_platform_data_account.reward_mode != REWARD_MODE_MINTED {
+            return Ok(());
+        }
 
-        match rarity {
-            "CO" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_sub(10)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        if Self::mintable_token_amount(unpacked_platform_data_account, token_amount) < token_amount
+        {
+            return Err(NFTStakingContractError::RewardMintCapExceeded.into());
+        }
 
-            "RA" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_sub(20)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        unpacked_platform_data_account.total_reward_minted += token_amount;
 
-            "EP" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_sub(50)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+        Ok(())
+    }
 
-            "LE" => {
-                unpacked_user_base_state_account.total_nft_points =
-                    unpacked_user_base_state_account
-                        .total_nft_points
-                        .checked_sub(100)
-                        .ok_or(NFTStakingContractError::MathError)?;
-            }
+    fn checkpoint_vesting(
+        unpacked_vesting_schedule_account: &mut VestingSchedule,
+        now: u64,
+    ) -> ProgramResult {
+        let release_until = now.min(unpacked_vesting_schedule_account.end_timestamp);
 
-            _ => return Err(NFTStakingContractError::InvalidNFTMetadata.into()),
+        if release_until <= unpacked_vesting_schedule_account.start_timestamp {
+            return Ok(());
         }
 
-        msg!("nft points {}", unpacked_user_base_state_account
-            .total_nft_points);
+        let released_amount = u128::from(unpacked_vesting_schedule_account.locked_amount)
+            .checked_mul(u128::from(
+                release_until - unpacked_vesting_schedule_account.start_timestamp,
+            ))
+            .ok_or(NFTStakingContractError::VestingOverflow)?
+            .checked_div(u128::from(
+                unpacked_vesting_schedule_account.end_timestamp
+                    - unpacked_vesting_schedule_account.start_timestamp,
+            ))
+            .ok_or(NFTStakingContractError::VestingOverflow)?;
+        let released_amount =
+            u64::try_from(released_amount).map_err(|_| NFTStakingContractError::VestingOverflow)?;
+
+        unpacked_vesting_schedule_account.locked_amount -= released_amount;
+        unpacked_vesting_schedule_account.unlocked_amount = unpacked_vesting_schedule_account
+            .unlocked_amount
+            .checked_add(released_amount)
+            .ok_or(NFTStakingContractError::VestingOverflow)?;
+        unpacked_vesting_schedule_account.start_timestamp = release_until;
 
-        let dividend_after = (100_u64)
+        Ok(())
+    }
+
+    fn add_vesting(
+        unpacked_vesting_schedule_account: &mut VestingSchedule,
+        token_amount: u64,
+        vesting_period_in_sec: u64,
+        now: u64,
+    ) -> ProgramResult {
+        Self::checkpoint_vesting(unpacked_vesting_schedule_account, now)?;
+
+        let locked_amount = unpacked_vesting_schedule_account
+            .locked_amount
+            .checked_add(token_amount)
+            .ok_or(NFTStakingContractError::VestingOverflow)?;
+
+        let remaining_in_sec = unpacked_vesting_schedule_account
+            .end_timestamp
+            .saturating_sub(now);
+
+        let vesting_in_sec = u128::from(unpacked_vesting_schedule_account.locked_amount)
+            .checked_mul(remaining_in_sec.into())
+            .and_then(|weighted| {
+                weighted.checked_add(
+                    u128::from(token_amount).checked_mul(vesting_period_in_sec.into())?,
+                )
+            })
+            .ok_or(NFTStakingContractError::VestingOverflow)?
+            .checked_div(locked_amount.into())
+            .ok_or(NFTStakingContractError::VestingOverflow)?;
+
+        unpacked_vesting_schedule_account.locked_amount = locked_amount;
+        unpacked_vesting_schedule_account.start_timestamp = now;
+        unpacked_vesting_schedule_account.end_timestamp = now
+            .checked_add(
+                u64::try_from(vesting_in_sec)
+                    .map_err(|_| NFTStakingContractError::VestingOverflow)?,
+            )
+            .ok_or(NFTStakingContractError::VestingOverflow)?;
+
+        Ok(())
+    }
+
+    fn partner_reward_shares(
+        unpacked_platform_data_account: &PlatformState,
+        unpacked_user_base_state_account: &UserBaseState,
+        stream: usize,
+    ) -> Result<u64, ProgramError> {
+        let dividend = (100_u64)
             .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
-        
-        msg!("dividend after {}", dividend_after);
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
 
-        if dividend_after > 0 {
-            unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
-                .total_staked_nfts
-                .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_div(dividend_after)
-                .ok_or(NFTStakingContractError::MathError)?;
-        }
+        let shares = unpacked_user_base_state_account
+            .total_staked_nfts
+            .checked_mul(unpacked_platform_data_account.partner_stream_reward_per_share[stream])
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+            .checked_mul(unpacked_user_base_state_account.total_nft_points)
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?
+            .checked_div(dividend)
+            .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+
+        Ok(shares)
+    }
 
-        msg!(
-            "Reward Debt After: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+    // A stream slot's generation changes whenever it is added or removed, so
+    // debts and owed rewards recorded against an earlier stream in the same
+    // slot are dropped before accruing against the current one.
+    fn sync_partner_stream_generations(
+        unpacked_platform_data_account: &PlatformState,
+        unpacked_user_base_state_account: &mut UserBaseState,
+    ) {
+        for stream in 0..MAX_PARTNER_REWARD_STREAMS {
+            let generation = unpacked_platform_data_account.partner_stream_generations[stream];
+
+            if unpacked_user_base_state_account.partner_stream_generations[stream] != generation {
+                unpacked_user_base_state_account.partner_stream_reward_debts[stream] = 0;
+                unpacked_user_base_state_account.partner_stream_owed[stream] = 0;
+                unpacked_user_base_state_account.partner_stream_generations[stream] = generation;
+            }
+        }
+    }
 
-        PlatformState::pack(
+    fn accrue_partner_rewards(
+        unpacked_platform_data_account: &PlatformState,
+        unpacked_user_base_state_account: &mut UserBaseState,
+    ) -> ProgramResult {
+        Self::sync_partner_stream_generations(
             unpacked_platform_data_account,
-            &mut platform_data_account.try_borrow_mut_data()?,
-        )?;
-
-        UserBaseState::pack(
             unpacked_user_base_state_account,
-            &mut user_base_state_account.try_borrow_mut_data()?,
-        )?;
+        );
 
-        Self::close_state_account(user_nft_state_account, user_account)?;
+        if unpacked_user_base_state_account.total_staked_nfts == 0 {
+            return Ok(());
+        }
 
-        msg!("Platform Data: {:?}", unpacked_platform_data_account);
-        msg!("User Base: {:?}", unpacked_user_base_state_account);
+        for stream in 0..usize::from(unpacked_platform_data_account.partner_stream_count) {
+            let pending_reward = Self::partner_reward_shares(
+                unpacked_platform_data_account,
+                unpacked_user_base_state_account,
+                stream,
+            )?
+            .checked_sub(unpacked_user_base_state_account.partner_stream_reward_debts[stream])
+            .ok_or(NFTStakingContractError::RewardDebtUnderflow)?;
+
+            unpacked_user_base_state_account.partner_stream_owed[stream] =
+                unpacked_user_base_state_account.partner_stream_owed[stream]
+                    .checked_add(pending_reward)
+                    .ok_or(NFTStakingContractError::PendingRewardOverflow)?;
+        }
 
         Ok(())
     }
 
-    fn process_claim_reward(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
+    fn reset_partner_reward_debts(
+        unpacked_platform_data_account: &PlatformState,
+        unpacked_user_base_state_account: &mut UserBaseState,
+    ) -> ProgramResult {
+        if unpacked_user_base_state_account.total_staked_nfts == 0 {
+            return Ok(());
+        }
 
-        let user_account = next_account_info(account_info_iter)?;
+        for stream in 0..usize::from(unpacked_platform_data_account.partner_stream_count) {
+            unpacked_user_base_state_account.partner_stream_reward_debts[stream] =
+                Self::partner_reward_shares(
+                    unpacked_platform_data_account,
+                    unpacked_user_base_state_account,
+                    stream,
+                )?;
+        }
 
-        let platform_data_account = next_account_info(account_info_iter)?;
+        Ok(())
+    }
 
-        let user_base_state_account = next_account_info(account_info_iter)?;
+    // Reward tokens and partner stream rewards still owed keep the user base
+    // state open so they can be collected with a later claim.
+    fn has_outstanding_rewards(unpacked_user_base_state_account: &UserBaseState) -> bool {
+        unpacked_user_base_state_account.reward_owed > 0
+            || unpacked_user_base_state_account
+                .partner_stream_owed
+                .iter()
+                .any(|owed| *owed > 0)
+    }
 
-        let reward_mint = next_account_info(account_info_iter)?;
+    // The user base state is closed once the user has fully exited: nothing
+    // staked, nothing unbonding and no rewards left to collect.
+    fn pack_or_close_user_base_state(
+        user_account: &AccountInfo,
+        user_base_state_account: &AccountInfo,
+        unpacked_user_base_state_account: UserBaseState,
+        clock: &Clock,
+    ) -> ProgramResult {
+        if unpacked_user_base_state_account.total_staked_nfts == 0
+            && unpacked_user_base_state_account.unbonding_nfts == 0
+            && !Self::has_outstanding_rewards(&unpacked_user_base_state_account)
+        {
+            Self::close_state_account(user_base_state_account, user_account)?;
+
+            NFTStakingContractEvent::UserAccountClosed(UserAccountClosed {
+                user: *user_account.key,
+                user_base_state: *user_base_state_account.key,
+                total_reward_claimed: unpacked_user_base_state_account.total_reward_claimed,
+                timestamp: clock.unix_timestamp,
+            })
+            .emit()?;
+        } else {
+            UserBaseState::pack(
+                unpacked_user_base_state_account,
+                &mut user_base_state_account.try_borrow_mut_data()?,
+            )?;
+        }
 
-        let user_reward_ata = next_account_info(account_info_iter)?;
+        Ok(())
+    }
 
-        let pda_reward_token_ata = next_account_info(account_info_iter)?;
+    fn find_user_base_state_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+        user_account: &AccountInfo,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                "user_base_state".as_bytes(),
+                platform_data_account.key.as_ref(),
+                user_account.key.as_ref(),
+            ],
+            program_id,
+        )
+    }
 
-        let pda_account = next_account_info(account_info_iter)?;
+    fn find_user_nft_state_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+        nft_mint: &AccountInfo,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                "user_nft_state".as_bytes(),
+                platform_data_account.key.as_ref(),
+                nft_mint.key.as_ref(),
+            ],
+            program_id,
+        )
+    }
 
-        let token_program_account = next_account_info(account_info_iter)?;
+    fn find_sol_treasury_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                "sol_treasury".as_bytes(),
+                platform_data_account.key.as_ref(),
+            ],
+            program_id,
+        )
+    }
 
-        let (pda, bump_seeds) = Pubkey::find_program_address(
+    fn find_vesting_schedule_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+        user_account: &AccountInfo,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
             &[
-                "nft_staking_contract".as_bytes(),
+                "vesting_schedule".as_bytes(),
                 platform_data_account.key.as_ref(),
+                user_account.key.as_ref(),
             ],
             program_id,
-        );
+        )
+    }
 
-        Validator::validate_is_signer(user_account)?;
-        Validator::validate_equality(*pda_account.key, pda)?;
-        Validator::validate_token_ata(user_reward_ata, reward_mint)?;
-        Validator::validate_token_owner(user_reward_ata, user_account)?;
-        Validator::validate_token_ata(pda_reward_token_ata, reward_mint)?;
-        Validator::validate_token_owner(pda_reward_token_ata, pda_account)?;
-        Validator::validate_state_account(platform_data_account, *program_id)?;
-        Validator::validate_state_account(user_base_state_account, *program_id)?;
+    fn find_mint_points_override_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+        nft_mint: &AccountInfo,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                "mint_points_override".as_bytes(),
+                platform_data_account.key.as_ref(),
+                nft_mint.key.as_ref(),
+            ],
+            program_id,
+        )
+    }
 
-        Self::update_pool(platform_data_account)?;
+    fn find_mint_list_entry_address(
+        program_id: &Pubkey,
+        platform_data_account: &AccountInfo,
+        nft_mint: &AccountInfo,
+        list: u8,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                "mint_list".as_bytes(),
+                &[list],
+                platform_data_account.key.as_ref(),
+                nft_mint.key.as_ref(),
+            ],
+            program_id,
+        )
+    }
 
-        let mut unpacked_user_base_state_account =
-            UserBaseState::unpack(&user_base_state_account.try_borrow_data()?)?;
+    fn apply_set_bonus(
+        user_account: &AccountInfo,
+        user_base_state_account: &AccountInfo,
+        unpacked_user_base_state_account: &mut UserBaseState,
+        unpacked_platform_data_account: &PlatformState,
+        clock: &Clock,
+    ) -> ProgramResult {
+        let nft_points = unpacked_user_base_state_account
+            .total_nft_points
+            .checked_sub(unpacked_user_base_state_account.set_bonus_points)
+            .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+
+        let set_bonus_points = if unpacked_user_base_state_account
+            .rarity_counts
+            .iter()
+            .all(|count| *count > 0)
+        {
+            nft_points
+                .checked_mul(
+                    u64::from(unpacked_platform_data_account.set_bonus_multiplier)
+                        .saturating_sub(100),
+                )
+                .ok_or(NFTStakingContractError::NftPointsOverflow)?
+                .checked_div(100)
+                .ok_or(NFTStakingContractError::NftPointsOverflow)?
+        } else {
+            0
+        };
 
-        Validator::validate_equality(unpacked_user_base_state_account.user, *user_account.key)?;
-        Validator::validate_equality(
-            unpacked_user_base_state_account.user_reward_ata,
-            *user_reward_ata.key,
-        )?;
+        if set_bonus_points == unpacked_user_base_state_account.set_bonus_points {
+            return Ok(());
+        }
 
-        let unpacked_platform_data_account =
-            PlatformState::unpack(&platform_data_account.try_borrow_data()?)?;
+        unpacked_user_base_state_account.total_nft_points = nft_points
+            .checked_add(set_bonus_points)
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+        unpacked_user_base_state_account.set_bonus_points = set_bonus_points;
+
+        NFTStakingContractEvent::SetBonusUpdated(SetBonusUpdated {
+            user: *user_account.key,
+            user_base_state: *user_base_state_account.key,
+            rarity_counts: unpacked_user_base_state_account.rarity_counts,
+            set_bonus_points,
+            user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+            timestamp: clock.unix_timestamp,
+        })
+        .emit()?;
 
-        Validator::validate_equality(unpacked_platform_data_account.reward_mint, *reward_mint.key)?;
-        Validator::validate_equality(
-            unpacked_platform_data_account.reward_token_ata,
-            *pda_reward_token_ata.key,
-        )?;
+        Ok(())
+    }
 
-        msg!(
-            "User Staked NFT's Count: {}",
-            unpacked_user_base_state_account.total_staked_nfts
-        );
-        msg!(
-            "Reward Per Share: {}",
-            unpacked_platform_data_account.reward_per_share
-        );
-        msg!(
-            "Reward Debt: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+    // A warming up NFT keeps its points in the pending bucket until its own
+    // warm-up ends and it is passed to the points refresh.
+    fn is_warming_up(unpacked_user_nft_state_account: &UserNFTState) -> bool {
+        unpacked_user_nft_state_account.warm_up_until != 0
+    }
 
-        msg!(
-            "Reward Debt Before: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+    fn loyalty_nft_points(
+        unpacked_platform_data_account: &PlatformState,
+        unpacked_user_nft_state_account: &UserNFTState,
+        clock: &Clock,
+    ) -> Result<u64, ProgramError> {
+        let staked_for =
+            (clock.unix_timestamp as u64).saturating_sub(unpacked_user_nft_state_account.staked_at);
+
+        let steps = staked_for
+            .checked_div(unpacked_platform_data_account.loyalty_step_in_sec)
+            .unwrap_or(0);
+
+        let loyalty_percentage = steps
+            .saturating_mul(
+                unpacked_platform_data_account
+                    .loyalty_step_percentage
+                    .into(),
+            )
+            .min(unpacked_platform_data_account.loyalty_max_percentage.into());
+
+        let nft_points = unpacked_user_nft_state_account
+            .base_nft_points
+            .checked_mul(
+                loyalty_percentage
+                    .checked_add(100)
+                    .ok_or(NFTStakingContractError::NftPointsOverflow)?,
+            )
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?
+            .checked_div(100)
+            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
 
-        let dividend = (100_u64)
-            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
+        Ok(nft_points)
+    }
 
-        if unpacked_user_base_state_account.total_staked_nfts > 0 {
-            let pending_reward = unpacked_user_base_state_account
-                .total_staked_nfts
-                .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_div(dividend)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_sub(unpacked_user_base_state_account.reward_debt)
-                .ok_or(NFTStakingContractError::MathError)?;
+    // Activates the points of NFTs whose warm-up has ended and applies the
+    // loyalty multiplier to the user's staked NFTs. Every staked NFT state
+    // except the one the instruction stakes or unstakes must be passed, so a
+    // user cannot refresh only the NFTs whose points go up.
+    fn refresh_nft_points(
+        program_id: &Pubkey,
+        user_base_state_account: &AccountInfo,
+        unpacked_user_base_state_account: &mut UserBaseState,
+        user_nft_state_accounts: &[AccountInfo],
+        skipped_user_nft_state: Option<&Pubkey>,
+        unpacked_platform_data_account: &PlatformState,
+        clock: &Clock,
+    ) -> ProgramResult {
+        let rent = Rent::get()?;
 
-            msg!("Pending Reward: {}", pending_reward / 100);
+        let expected_user_nft_states = unpacked_user_base_state_account
+            .total_staked_nfts
+            .checked_sub(u64::from(skipped_user_nft_state.is_some()))
+            .ok_or(NFTStakingContractError::StakedCountUnderflow)?;
 
-            // pending_reward = pending_reward
-            //     .checked_div(dividend)
-            //     .ok_or(NFTStakingContractError::MathError)?;
+        if user_nft_state_accounts.len() as u64 != expected_user_nft_states {
+            return Err(NFTStakingContractError::IncompleteUserNftStates.into());
+        }
 
-            // pending_reward = pending_reward
-            //     .checked_mul(unpacked_user_base_state_account.total_nft_points)
-            //     .ok_or(NFTStakingContractError::MathError)?;
+        for (index, user_nft_state_account) in user_nft_state_accounts.iter().enumerate() {
+            let duplicate = skipped_user_nft_state == Some(user_nft_state_account.key)
+                || user_nft_state_accounts[..index]
+                    .iter()
+                    .any(|account| account.key == user_nft_state_account.key);
 
-            // msg!("Pending Reward: {}", pending_reward / 100);
+            if duplicate {
+                return Err(NFTStakingContractError::IncompleteUserNftStates.into());
+            }
 
-            if pending_reward > 0 {
-                let transfer_reward_ix = spl_token::instruction::transfer(
-                    &spl_token::id(),
-                    pda_reward_token_ata.key,
-                    user_reward_ata.key,
-                    pda_account.key,
-                    &[],
-                    pending_reward * 1000000,
-                )?;
+            Validator::validate_state_account(user_nft_state_account, *program_id)?;
+            Validator::validate_rent_exempt(user_nft_state_account, &rent)?;
 
-                invoke_signed(
-                    &transfer_reward_ix,
-                    &[
-                        pda_reward_token_ata.clone(),
-                        user_reward_ata.clone(),
-                        pda_account.clone(),
-                        token_program_account.clone(),
-                    ],
-                    &[&[
-                        "nft_staking_contract".as_bytes(),
-                        platform_data_account.key.as_ref(),
-                        &[bump_seeds],
-                    ]],
-                )?;
+            let mut unpacked_user_nft_state_account =
+                UserNFTState::unpack(&user_nft_state_account.try_borrow_data()?)?;
+
+            Validator::validate_equality(
+                unpacked_user_nft_state_account.user_base_state,
+                *user_base_state_account.key,
+                NFTStakingContractError::UserBaseStateMismatch,
+            )?;
 
-                unpacked_user_base_state_account.total_reward_claimed =
+            if unpacked_user_nft_state_account.cooldown_end_timestamp != 0 {
+                return Err(NFTStakingContractError::IncompleteUserNftStates.into());
+            }
+
+            let old_nft_points = unpacked_user_nft_state_account.nft_points;
+
+            let nft_points = Self::loyalty_nft_points(
+                unpacked_platform_data_account,
+                &unpacked_user_nft_state_account,
+                clock,
+            )?;
+
+            let warm_up_ended = Self::is_warming_up(&unpacked_user_nft_state_account)
+                && (clock.unix_timestamp as u64) >= unpacked_user_nft_state_account.warm_up_until;
+
+            if warm_up_ended {
+                unpacked_user_base_state_account.pending_nft_points =
+                    unpacked_user_base_state_account
+                        .pending_nft_points
+                        .checked_sub(old_nft_points)
+                        .ok_or(NFTStakingContractError::NftPointsUnderflow)?;
+                unpacked_user_base_state_account.total_nft_points =
                     unpacked_user_base_state_account
-                        .total_reward_claimed
-                        .checked_add(pending_reward)
-                        .ok_or(NFTStakingContractError::MathError)?;
+                        .total_nft_points
+                        .checked_add(old_nft_points)
+                        .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+                unpacked_user_nft_state_account.warm_up_until = 0;
+
+                NFTStakingContractEvent::NftPointsActivated(NftPointsActivated {
+                    user: unpacked_user_nft_state_account.user,
+                    user_base_state: *user_base_state_account.key,
+                    activated_nft_points: old_nft_points,
+                    user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+                    timestamp: clock.unix_timestamp,
+                })
+                .emit()?;
             }
-        }
-        
-        msg!("nft points {}", unpacked_user_base_state_account
-            .total_nft_points);
 
-        let dividend_after = (100_u64)
-            .checked_mul(unpacked_user_base_state_account.total_staked_nfts)
-            .ok_or(NFTStakingContractError::MathError)?;
-        
-        msg!("dividend after {}", dividend_after);
+            if nft_points != old_nft_points {
+                if Self::is_warming_up(&unpacked_user_nft_state_account) {
+                    unpacked_user_base_state_account.pending_nft_points =
+                        unpacked_user_base_state_account
+                            .pending_nft_points
+                            .checked_sub(old_nft_points)
+                            .ok_or(NFTStakingContractError::NftPointsUnderflow)?
+                            .checked_add(nft_points)
+                            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+                } else {
+                    unpacked_user_base_state_account.total_nft_points =
+                        unpacked_user_base_state_account
+                            .total_nft_points
+                            .checked_sub(old_nft_points)
+                            .ok_or(NFTStakingContractError::NftPointsUnderflow)?
+                            .checked_add(nft_points)
+                            .ok_or(NFTStakingContractError::NftPointsOverflow)?;
+                }
+
+                unpacked_user_nft_state_account.nft_points = nft_points;
+
+                NFTStakingContractEvent::LoyaltyPointsUpdated(LoyaltyPointsUpdated {
+                    user: unpacked_user_nft_state_account.user,
+                    user_base_state: *user_base_state_account.key,
+                    user_nft_state: *user_nft_state_account.key,
+                    nft_mint: unpacked_user_nft_state_account.nft_mint,
+                    base_nft_points: unpacked_user_nft_state_account.base_nft_points,
+                    nft_points,
+                    user_total_nft_points: unpacked_user_base_state_account.total_nft_points,
+                    timestamp: clock.unix_timestamp,
+                })
+                .emit()?;
+            }
 
-        if dividend_after > 0 {
-            unpacked_user_base_state_account.reward_debt = unpacked_user_base_state_account
-                .total_staked_nfts
-                .checked_mul(unpacked_platform_data_account.reward_per_share)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_mul(unpacked_user_base_state_account.total_nft_points)
-                .ok_or(NFTStakingContractError::MathError)?
-                .checked_div(dividend_after)
-                .ok_or(NFTStakingContractError::MathError)?;
+            if warm_up_ended || nft_points != old_nft_points {
+                UserNFTState::pack(
+                    unpacked_user_nft_state_account,
+                    &mut user_nft_state_account.try_borrow_mut_data()?,
+                )?;
+            }
         }
 
-        msg!(
-            "Reward Debt After: {}",
-            unpacked_user_base_state_account.reward_debt
-        );
+        Ok(())
+    }
 
-        UserBaseState::pack(
-            unpacked_user_base_state_account,
-            &mut user_base_state_account.try_borrow_mut_data()?,
-        )?;
+    // Recycled rewards are already in reward units, so they are spread over
+    // NFT points like a bonus rather than over staked NFTs like the
+    // emission. The rounding remainder stays recycled for the next update.
+    fn distribute_recycled_reward(
+        unpacked_platform_data_account: &mut PlatformState,
+    ) -> ProgramResult {
+        if unpacked_platform_data_account.recycled_reward == 0
+            || unpacked_platform_data_account.total_nft_points == 0
+        {
+            return Ok(());
+        }
 
-        msg!("Platform Data: {:?}", unpacked_platform_data_account);
-        msg!("User Base: {:?}", unpacked_user_base_state_account);
+        let reward_per_share_increase = unpacked_platform_data_account
+            .recycled_reward
+            .checked_mul(100)
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?
+            / unpacked_platform_data_account.total_nft_points;
+
+        // Rounded up so repeated updates cannot keep raising the reward per
+        // share with a remainder that is never consumed.
+        let distributed_reward = reward_per_share_increase
+            .checked_mul(unpacked_platform_data_account.total_nft_points)
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?
+            .checked_add(99)
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?
+            / 100;
+
+        unpacked_platform_data_account.reward_per_share = unpacked_platform_data_account
+            .reward_per_share
+            .checked_add(reward_per_share_increase)
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?;
+        unpacked_platform_data_account.recycled_reward -= distributed_reward;
 
         Ok(())
     }
 
+    fn boosted_accumulation_periods(
+        unpacked_platform_data_account: &PlatformState,
+        now: u64,
+    ) -> Result<u64, ProgramError> {
+        let last_updated = unpacked_platform_data_account.last_updated;
+
+        let mut weighted_interval = u128::from(now - last_updated) * 100;
 
+        for window in 0..usize::from(unpacked_platform_data_account.boost_window_count) {
+            let overlap_start =
+                unpacked_platform_data_account.boost_window_starts[window].max(last_updated);
+            let overlap_end = unpacked_platform_data_account.boost_window_ends[window].min(now);
 
-    
+            if overlap_end > overlap_start {
+                weighted_interval += u128::from(overlap_end - overlap_start)
+                    * u128::from(
+                        unpacked_platform_data_account.boost_window_multipliers[window] - 100,
+                    );
+            }
+        }
+
+        u64::try_from(
+            weighted_interval
+                / (u128::from(unpacked_platform_data_account.reward_accumulation_in_sec) * 100),
+        )
+        .map_err(|_| NFTStakingContractError::RewardGeneratedOverflow.into())
+    }
 
     fn update_pool(platform_data_account: &AccountInfo) -> ProgramResult {
         let mut unpacked_platform_data_account =
@@ -961,6 +4924,8 @@ impl Processor {
         if unpacked_platform_data_account.total_staked_nfts == 0 {
             unpacked_platform_data_account.last_updated = clock.unix_timestamp as u64;
             unpacked_platform_data_account.reward_per_share = 0;
+            unpacked_platform_data_account.partner_stream_reward_per_share =
+                [0; MAX_PARTNER_REWARD_STREAMS];
             PlatformState::pack(
                 unpacked_platform_data_account,
                 &mut platform_data_account.try_borrow_mut_data()?,
@@ -971,63 +4936,88 @@ impl Processor {
 
         let interval = clock.unix_timestamp as u64 - unpacked_platform_data_account.last_updated;
 
-        msg!("Interval: {}", interval);
-
         let multiplier = interval
             .checked_div(unpacked_platform_data_account.reward_accumulation_in_sec)
-            .ok_or(NFTStakingContractError::MathError)?;
-
-        msg!("Multiplier: {}", multiplier);
+            .ok_or(NFTStakingContractError::ZeroRewardAccumulationInterval)?;
 
         let total_coin_emission = unpacked_platform_data_account.total_coin_emission;
 
-        msg!(
-            "Total Coin Emission: {} for {} seconds",
-            total_coin_emission,
-            unpacked_platform_data_account.coin_emission_distribution_in_sec
-        );
-
         let reward_per_multiplier = ((total_coin_emission as f64)
             / unpacked_platform_data_account.coin_emission_distribution_in_sec as f64)
             * unpacked_platform_data_account.reward_accumulation_in_sec as f64
             * 100.00;
 
-        msg!("Reward Per Multiplier: {}", reward_per_multiplier);
+        let boosted_multiplier = if multiplier > 0 {
+            Self::boosted_accumulation_periods(
+                &unpacked_platform_data_account,
+                clock.unix_timestamp as u64,
+            )?
+        } else {
+            0
+        };
 
-        let reward_generated = multiplier
+        let reward_generated = boosted_multiplier
             .checked_mul(reward_per_multiplier as u64)
-            .ok_or(NFTStakingContractError::MathError)?;
-
-        msg!("Reward Generated: {}", reward_generated);
+            .ok_or(NFTStakingContractError::RewardGeneratedOverflow)?;
 
         unpacked_platform_data_account.reward_per_share = unpacked_platform_data_account
             .reward_per_share
             .checked_add(
                 reward_generated
                     .checked_div(unpacked_platform_data_account.total_staked_nfts)
-                    .ok_or(NFTStakingContractError::MathError)?,
+                    .ok_or(NFTStakingContractError::RewardPerShareOverflow)?,
             )
-            .ok_or(NFTStakingContractError::MathError)?;
-
-        msg!(
-            "Reward Per Share: {}",
-            unpacked_platform_data_account.reward_per_share
-        );
+            .ok_or(NFTStakingContractError::RewardPerShareOverflow)?;
+
+        Self::distribute_recycled_reward(&mut unpacked_platform_data_account)?;
+
+        // Partner streams accrue on the plain accumulation periods: boost
+        // windows only scale the primary reward emission. Their share is
+        // spread over NFT points the same way as the user reward shares.
+        if unpacked_platform_data_account.total_nft_points > 0 {
+            for stream in 0..usize::from(unpacked_platform_data_account.partner_stream_count) {
+                let partner_reward_generated = multiplier
+                    .checked_mul(
+                        unpacked_platform_data_account.partner_stream_emission_per_sec[stream],
+                    )
+                    .and_then(|reward| {
+                        reward
+                            .checked_mul(unpacked_platform_data_account.reward_accumulation_in_sec)
+                    })
+                    .and_then(|reward| reward.checked_mul(100))
+                    .ok_or(NFTStakingContractError::RewardGeneratedOverflow)?;
+
+                unpacked_platform_data_account.partner_stream_reward_per_share[stream] =
+                    unpacked_platform_data_account.partner_stream_reward_per_share[stream]
+                        .checked_add(
+                            partner_reward_generated
+                                .checked_div(unpacked_platform_data_account.total_nft_points)
+                                .ok_or(NFTStakingContractError::RewardPerShareOverflow)?,
+                        )
+                        .ok_or(NFTStakingContractError::RewardPerShareOverflow)?;
+            }
+        }
 
         if multiplier > 0 {
             unpacked_platform_data_account.last_updated = clock.unix_timestamp as u64;
         }
 
-        msg!(
-            "Last Updated: {}",
-            unpacked_platform_data_account.last_updated
-        );
-
         PlatformState::pack(
             unpacked_platform_data_account,
             &mut platform_data_account.try_borrow_mut_data()?,
         )?;
 
+        if multiplier > 0 {
+            NFTStakingContractEvent::PoolUpdated(PoolUpdated {
+                platform: *platform_data_account.key,
+                reward_generated,
+                reward_per_share: unpacked_platform_data_account.reward_per_share,
+                total_staked_nfts: unpacked_platform_data_account.total_staked_nfts,
+                last_updated: unpacked_platform_data_account.last_updated,
+            })
+            .emit()?;
+        }
+
         Ok(())
     }
 
@@ -1038,11 +5028,17 @@ impl Processor {
         let state_account_account_initial_lamports = dest_account.lamports();
         **dest_account.lamports.borrow_mut() = state_account_account_initial_lamports
             .checked_add(state_account.lamports())
-            .ok_or(NFTStakingContractError::MathError)?;
+            .ok_or(NFTStakingContractError::LamportsOverflow)?;
         **state_account.lamports.borrow_mut() = 0;
 
-        let mut state_account_lamports = state_account.data.borrow_mut();
-        state_account_lamports.fill(0);
+        state_account.try_borrow_mut_data()?.fill(0);
+
+        // The runtime only lets a program give away an account whose data is
+        // zeroed, so the closed account cannot also keep a closed marker. Once
+        // owned by the system program, an account revived within the same
+        // transaction is rejected by the owner check in
+        // `validate_state_account`.
+        state_account.assign(&system_program::id());
 
         Ok(())
     }
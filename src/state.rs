@@ -5,6 +5,126 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub const DEFAULT_POOL_NAME: &str = "NFT Staking Main";
+
+pub const MAX_POOL_NAME_LEN: usize = 32;
+
+pub const LOCK_TIERS: usize = 4;
+
+pub const RARITY_TIERS: usize = 4;
+
+pub const RARITY_CODES: [&str; RARITY_TIERS] = ["CO", "RA", "EP", "LE"];
+
+pub const RARITY_POINTS: [u64; RARITY_TIERS] = [10, 20, 50, 100];
+
+pub const NO_RARITY_TIER: u8 = u8::MAX;
+
+pub const MINT_LIST_MODE_OPEN: u8 = 0;
+
+pub const MINT_LIST_MODE_ALLOWLIST: u8 = 1;
+
+pub const MINT_LIST_MODE_DENYLIST: u8 = 2;
+
+pub const MAX_RARITY_PROOF_LEN: usize = 24;
+
+pub const MAX_PARTNER_REWARD_STREAMS: usize = 2;
+
+pub const REWARD_MODE_VAULT: u8 = 0;
+
+pub const REWARD_MODE_MINTED: u8 = 1;
+
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1000;
+
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+pub const MAX_STAKE_FEE_LAMPORTS: u64 = 1_000_000_000;
+
+pub const MAX_VESTING_PERIOD_IN_SEC: u64 = 365 * 86400;
+
+pub const MIN_CLAIM_WINDOW_IN_SEC: u64 = 86400;
+
+pub const MAX_CLAIM_WINDOW_IN_SEC: u64 = 365 * 86400;
+
+pub const MAX_BOOST_WINDOWS: usize = 4;
+
+pub const MAX_BOOST_MULTIPLIER: u16 = 1000;
+
+pub const DEFAULT_LOCK_TIER_DURATIONS_IN_SEC: [u64; LOCK_TIERS] =
+    [0, 30 * 86400, 90 * 86400, 180 * 86400];
+
+pub const DEFAULT_LOCK_TIER_MULTIPLIERS: [u16; LOCK_TIERS] = [100, 110, 125, 150];
+
+pub const MAX_LOCK_TIER_MULTIPLIER: u16 = 1000;
+
+pub const DEFAULT_UNSTAKE_COOLDOWN_IN_SEC: u64 = 0;
+
+pub const MAX_UNSTAKE_COOLDOWN_IN_SEC: u64 = 30 * 86400;
+
+pub const MAX_EARLY_EXIT_PENALTY_PERCENTAGE: u8 = 100;
+
+pub const DEFAULT_WARM_UP_PERIOD_IN_SEC: u64 = 0;
+
+pub const MAX_WARM_UP_PERIOD_IN_SEC: u64 = 30 * 86400;
+
+pub const DEFAULT_LOYALTY_STEP_IN_SEC: u64 = 30 * 86400;
+
+pub const DEFAULT_LOYALTY_STEP_PERCENTAGE: u16 = 10;
+
+pub const DEFAULT_LOYALTY_MAX_PERCENTAGE: u16 = 50;
+
+pub const MAX_LOYALTY_BONUS_PERCENTAGE: u16 = 900;
+
+pub const DEFAULT_SET_BONUS_MULTIPLIER: u16 = 110;
+
+pub const MAX_SET_BONUS_MULTIPLIER: u16 = 1000;
+
+fn unpack_u64_array<const N: usize>(src: &[u8]) -> [u64; N] {
+    let mut dst = [0u64; N];
+    for (value, bytes) in dst.iter_mut().zip(src.chunks_exact(8)) {
+        *value = u64::from_le_bytes(*array_ref![bytes, 0, 8]);
+    }
+    dst
+}
+
+fn pack_u64_array(src: &[u64], dst: &mut [u8]) {
+    for (value, bytes) in src.iter().zip(dst.chunks_exact_mut(8)) {
+        bytes.copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn unpack_pubkey_array<const N: usize>(src: &[u8]) -> [Pubkey; N] {
+    let mut dst = [Pubkey::default(); N];
+    for (value, bytes) in dst.iter_mut().zip(src.chunks_exact(32)) {
+        *value = Pubkey::new_from_array(*array_ref![bytes, 0, 32]);
+    }
+    dst
+}
+
+fn pack_pubkey_array(src: &[Pubkey], dst: &mut [u8]) {
+    for (value, bytes) in src.iter().zip(dst.chunks_exact_mut(32)) {
+        bytes.copy_from_slice(value.as_ref());
+    }
+}
+
+fn unpack_u16_array<const N: usize>(src: &[u8]) -> [u16; N] {
+    let mut dst = [0u16; N];
+    for (value, bytes) in dst.iter_mut().zip(src.chunks_exact(2)) {
+        *value = u16::from_le_bytes(*array_ref![bytes, 0, 2]);
+    }
+    dst
+}
+
+fn pack_u16_array(src: &[u16], dst: &mut [u8]) {
+    for (value, bytes) in src.iter().zip(dst.chunks_exact_mut(2)) {
+        bytes.copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+// The state layouts carry no version byte and have changed size since the
+// original deployment, so `unpack` rejects accounts created with an older
+// layout by length. There is no in-place migration: upgrading an existing
+// deployment requires a fresh program deployment with new platform and user
+// state accounts, after users have unstaked from the old one.
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct PlatformState {
     pub is_initialized: bool,
@@ -18,6 +138,40 @@ pub struct PlatformState {
     pub reward_mint: Pubkey,
     pub reward_token_ata: Pubkey,
     pub pda_account: Pubkey,
+    pub lock_tier_durations_in_sec: [u64; LOCK_TIERS],
+    pub lock_tier_multipliers: [u16; LOCK_TIERS],
+    pub unstake_cooldown_in_sec: u64,
+    pub early_exit_penalty_percentage: u8,
+    pub early_exit_penalty_to_treasury: bool,
+    pub penalty_treasury_ata: Pubkey,
+    pub warm_up_period_in_sec: u64,
+    pub loyalty_step_in_sec: u64,
+    pub loyalty_step_percentage: u16,
+    pub loyalty_max_percentage: u16,
+    pub set_bonus_multiplier: u16,
+    pub mint_list_mode: u8,
+    pub rarity_merkle_root: [u8; 32],
+    pub pool_name: [u8; MAX_POOL_NAME_LEN],
+    pub partner_stream_count: u8,
+    pub partner_stream_mints: [Pubkey; MAX_PARTNER_REWARD_STREAMS],
+    pub partner_stream_vaults: [Pubkey; MAX_PARTNER_REWARD_STREAMS],
+    pub partner_stream_emission_per_sec: [u64; MAX_PARTNER_REWARD_STREAMS],
+    pub partner_stream_reward_per_share: [u64; MAX_PARTNER_REWARD_STREAMS],
+    pub reward_mode: u8,
+    pub reward_mint_cap: u64,
+    pub total_reward_minted: u64,
+    pub protocol_fee_bps: u16,
+    pub protocol_fee_treasury_ata: Pubkey,
+    pub stake_fee_lamports: u64,
+    pub vesting_period_in_sec: u64,
+    pub claim_window_in_sec: u64,
+    pub recycled_reward: u64,
+    pub total_nft_points: u64,
+    pub boost_window_count: u8,
+    pub boost_window_starts: [u64; MAX_BOOST_WINDOWS],
+    pub boost_window_ends: [u64; MAX_BOOST_WINDOWS],
+    pub boost_window_multipliers: [u16; MAX_BOOST_WINDOWS],
+    pub partner_stream_generations: [u64; MAX_PARTNER_REWARD_STREAMS],
 }
 
 impl Sealed for PlatformState {}
@@ -28,7 +182,7 @@ impl IsInitialized for PlatformState {
 }
 
 impl Pack for PlatformState {
-    const LEN: usize = 146;
+    const LEN: usize = 656;
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
         let src = array_ref![src, 0, PlatformState::LEN];
@@ -45,7 +199,44 @@ impl Pack for PlatformState {
             reward_mint,
             reward_token_ata,
             pda_account,
-        ) = array_refs![src, 1, 1, 8, 8, 8, 8, 8, 8, 32, 32, 32];
+            lock_tier_durations_in_sec,
+            lock_tier_multipliers,
+            unstake_cooldown_in_sec,
+            early_exit_penalty_percentage,
+            early_exit_penalty_to_treasury,
+            penalty_treasury_ata,
+            warm_up_period_in_sec,
+            loyalty_step_in_sec,
+            loyalty_step_percentage,
+            loyalty_max_percentage,
+            set_bonus_multiplier,
+            mint_list_mode,
+            rarity_merkle_root,
+            pool_name,
+            partner_stream_count,
+            partner_stream_mints,
+            partner_stream_vaults,
+            partner_stream_emission_per_sec,
+            partner_stream_reward_per_share,
+            reward_mode,
+            reward_mint_cap,
+            total_reward_minted,
+            protocol_fee_bps,
+            protocol_fee_treasury_ata,
+            stake_fee_lamports,
+            vesting_period_in_sec,
+            claim_window_in_sec,
+            recycled_reward,
+            total_nft_points,
+            boost_window_count,
+            boost_window_starts,
+            boost_window_ends,
+            boost_window_multipliers,
+            partner_stream_generations,
+        ) = array_refs![
+            src, 1, 1, 8, 8, 8, 8, 8, 8, 32, 32, 32, 32, 8, 8, 1, 1, 32, 8, 8, 2, 2, 2, 1, 32, 32,
+            1, 64, 64, 16, 16, 1, 8, 8, 2, 32, 8, 8, 8, 8, 8, 1, 32, 32, 8, 16
+        ];
 
         let is_initialized = match is_initialized {
             [0] => false,
@@ -53,6 +244,12 @@ impl Pack for PlatformState {
             _ => return Err(ProgramError::InvalidAccountData),
         };
 
+        let early_exit_penalty_to_treasury = match early_exit_penalty_to_treasury {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
         Ok(PlatformState {
             is_initialized,
             coin_emission_percentage: u8::from_le_bytes(*coin_emission_percentage),
@@ -67,6 +264,40 @@ impl Pack for PlatformState {
             reward_mint: Pubkey::new_from_array(*reward_mint),
             reward_token_ata: Pubkey::new_from_array(*reward_token_ata),
             pda_account: Pubkey::new_from_array(*pda_account),
+            lock_tier_durations_in_sec: unpack_u64_array(lock_tier_durations_in_sec),
+            lock_tier_multipliers: unpack_u16_array(lock_tier_multipliers),
+            unstake_cooldown_in_sec: u64::from_le_bytes(*unstake_cooldown_in_sec),
+            early_exit_penalty_percentage: u8::from_le_bytes(*early_exit_penalty_percentage),
+            early_exit_penalty_to_treasury,
+            penalty_treasury_ata: Pubkey::new_from_array(*penalty_treasury_ata),
+            warm_up_period_in_sec: u64::from_le_bytes(*warm_up_period_in_sec),
+            loyalty_step_in_sec: u64::from_le_bytes(*loyalty_step_in_sec),
+            loyalty_step_percentage: u16::from_le_bytes(*loyalty_step_percentage),
+            loyalty_max_percentage: u16::from_le_bytes(*loyalty_max_percentage),
+            set_bonus_multiplier: u16::from_le_bytes(*set_bonus_multiplier),
+            mint_list_mode: u8::from_le_bytes(*mint_list_mode),
+            rarity_merkle_root: *rarity_merkle_root,
+            pool_name: *pool_name,
+            partner_stream_count: u8::from_le_bytes(*partner_stream_count),
+            partner_stream_mints: unpack_pubkey_array(partner_stream_mints),
+            partner_stream_vaults: unpack_pubkey_array(partner_stream_vaults),
+            partner_stream_emission_per_sec: unpack_u64_array(partner_stream_emission_per_sec),
+            partner_stream_reward_per_share: unpack_u64_array(partner_stream_reward_per_share),
+            reward_mode: u8::from_le_bytes(*reward_mode),
+            reward_mint_cap: u64::from_le_bytes(*reward_mint_cap),
+            total_reward_minted: u64::from_le_bytes(*total_reward_minted),
+            protocol_fee_bps: u16::from_le_bytes(*protocol_fee_bps),
+            protocol_fee_treasury_ata: Pubkey::new_from_array(*protocol_fee_treasury_ata),
+            stake_fee_lamports: u64::from_le_bytes(*stake_fee_lamports),
+            vesting_period_in_sec: u64::from_le_bytes(*vesting_period_in_sec),
+            claim_window_in_sec: u64::from_le_bytes(*claim_window_in_sec),
+            recycled_reward: u64::from_le_bytes(*recycled_reward),
+            total_nft_points: u64::from_le_bytes(*total_nft_points),
+            boost_window_count: u8::from_le_bytes(*boost_window_count),
+            boost_window_starts: unpack_u64_array(boost_window_starts),
+            boost_window_ends: unpack_u64_array(boost_window_ends),
+            boost_window_multipliers: unpack_u16_array(boost_window_multipliers),
+            partner_stream_generations: unpack_u64_array(partner_stream_generations),
         })
     }
 
@@ -85,7 +316,44 @@ impl Pack for PlatformState {
             reward_mint_dst,
             reward_token_ata_dst,
             pda_account_dst,
-        ) = mut_array_refs![dst, 1, 1, 8, 8, 8, 8, 8, 8, 32, 32, 32];
+            lock_tier_durations_in_sec_dst,
+            lock_tier_multipliers_dst,
+            unstake_cooldown_in_sec_dst,
+            early_exit_penalty_percentage_dst,
+            early_exit_penalty_to_treasury_dst,
+            penalty_treasury_ata_dst,
+            warm_up_period_in_sec_dst,
+            loyalty_step_in_sec_dst,
+            loyalty_step_percentage_dst,
+            loyalty_max_percentage_dst,
+            set_bonus_multiplier_dst,
+            mint_list_mode_dst,
+            rarity_merkle_root_dst,
+            pool_name_dst,
+            partner_stream_count_dst,
+            partner_stream_mints_dst,
+            partner_stream_vaults_dst,
+            partner_stream_emission_per_sec_dst,
+            partner_stream_reward_per_share_dst,
+            reward_mode_dst,
+            reward_mint_cap_dst,
+            total_reward_minted_dst,
+            protocol_fee_bps_dst,
+            protocol_fee_treasury_ata_dst,
+            stake_fee_lamports_dst,
+            vesting_period_in_sec_dst,
+            claim_window_in_sec_dst,
+            recycled_reward_dst,
+            total_nft_points_dst,
+            boost_window_count_dst,
+            boost_window_starts_dst,
+            boost_window_ends_dst,
+            boost_window_multipliers_dst,
+            partner_stream_generations_dst,
+        ) = mut_array_refs![
+            dst, 1, 1, 8, 8, 8, 8, 8, 8, 32, 32, 32, 32, 8, 8, 1, 1, 32, 8, 8, 2, 2, 2, 1, 32, 32,
+            1, 64, 64, 16, 16, 1, 8, 8, 2, 32, 8, 8, 8, 8, 8, 1, 32, 32, 8, 16
+        ];
 
         let PlatformState {
             is_initialized,
@@ -99,6 +367,40 @@ impl Pack for PlatformState {
             reward_mint,
             reward_token_ata,
             pda_account,
+            lock_tier_durations_in_sec,
+            lock_tier_multipliers,
+            unstake_cooldown_in_sec,
+            early_exit_penalty_percentage,
+            early_exit_penalty_to_treasury,
+            penalty_treasury_ata,
+            warm_up_period_in_sec,
+            loyalty_step_in_sec,
+            loyalty_step_percentage,
+            loyalty_max_percentage,
+            set_bonus_multiplier,
+            mint_list_mode,
+            rarity_merkle_root,
+            pool_name,
+            partner_stream_count,
+            partner_stream_mints,
+            partner_stream_vaults,
+            partner_stream_emission_per_sec,
+            partner_stream_reward_per_share,
+            reward_mode,
+            reward_mint_cap,
+            total_reward_minted,
+            protocol_fee_bps,
+            protocol_fee_treasury_ata,
+            stake_fee_lamports,
+            vesting_period_in_sec,
+            claim_window_in_sec,
+            recycled_reward,
+            total_nft_points,
+            boost_window_count,
+            boost_window_starts,
+            boost_window_ends,
+            boost_window_multipliers,
+            partner_stream_generations,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
@@ -112,18 +414,69 @@ impl Pack for PlatformState {
         reward_mint_dst.copy_from_slice(reward_mint.as_ref());
         reward_token_ata_dst.copy_from_slice(reward_token_ata.as_ref());
         pda_account_dst.copy_from_slice(pda_account.as_ref());
+        pack_u64_array(lock_tier_durations_in_sec, lock_tier_durations_in_sec_dst);
+        pack_u16_array(lock_tier_multipliers, lock_tier_multipliers_dst);
+        *unstake_cooldown_in_sec_dst = unstake_cooldown_in_sec.to_le_bytes();
+        *early_exit_penalty_percentage_dst = early_exit_penalty_percentage.to_le_bytes();
+        early_exit_penalty_to_treasury_dst[0] = *early_exit_penalty_to_treasury as u8;
+        penalty_treasury_ata_dst.copy_from_slice(penalty_treasury_ata.as_ref());
+        *warm_up_period_in_sec_dst = warm_up_period_in_sec.to_le_bytes();
+        *loyalty_step_in_sec_dst = loyalty_step_in_sec.to_le_bytes();
+        *loyalty_step_percentage_dst = loyalty_step_percentage.to_le_bytes();
+        *loyalty_max_percentage_dst = loyalty_max_percentage.to_le_bytes();
+        *set_bonus_multiplier_dst = set_bonus_multiplier.to_le_bytes();
+        *mint_list_mode_dst = mint_list_mode.to_le_bytes();
+        rarity_merkle_root_dst.copy_from_slice(rarity_merkle_root);
+        pool_name_dst.copy_from_slice(pool_name);
+        *partner_stream_count_dst = partner_stream_count.to_le_bytes();
+        pack_pubkey_array(partner_stream_mints, partner_stream_mints_dst);
+        pack_pubkey_array(partner_stream_vaults, partner_stream_vaults_dst);
+        pack_u64_array(
+            partner_stream_emission_per_sec,
+            partner_stream_emission_per_sec_dst,
+        );
+        pack_u64_array(
+            partner_stream_reward_per_share,
+            partner_stream_reward_per_share_dst,
+        );
+        *reward_mode_dst = reward_mode.to_le_bytes();
+        *reward_mint_cap_dst = reward_mint_cap.to_le_bytes();
+        *total_reward_minted_dst = total_reward_minted.to_le_bytes();
+        *protocol_fee_bps_dst = protocol_fee_bps.to_le_bytes();
+        protocol_fee_treasury_ata_dst.copy_from_slice(protocol_fee_treasury_ata.as_ref());
+        *stake_fee_lamports_dst = stake_fee_lamports.to_le_bytes();
+        *vesting_period_in_sec_dst = vesting_period_in_sec.to_le_bytes();
+        *claim_window_in_sec_dst = claim_window_in_sec.to_le_bytes();
+        *recycled_reward_dst = recycled_reward.to_le_bytes();
+        *total_nft_points_dst = total_nft_points.to_le_bytes();
+        *boost_window_count_dst = boost_window_count.to_le_bytes();
+        pack_u64_array(boost_window_starts, boost_window_starts_dst);
+        pack_u64_array(boost_window_ends, boost_window_ends_dst);
+        pack_u16_array(boost_window_multipliers, boost_window_multipliers_dst);
+        pack_u64_array(partner_stream_generations, partner_stream_generations_dst);
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct UserBaseState {
     pub is_initialized: bool,
+    pub platform: Pubkey,
     pub user: Pubkey,
     pub user_reward_ata: Pubkey,
     pub total_staked_nfts: u64,
     pub total_nft_points: u64,
     pub total_reward_claimed: u64,
     pub reward_debt: u64,
+    pub pending_nft_points: u64,
+    pub rarity_counts: [u64; RARITY_TIERS],
+    pub set_bonus_points: u64,
+    pub partner_stream_reward_debts: [u64; MAX_PARTNER_REWARD_STREAMS],
+    pub partner_stream_owed: [u64; MAX_PARTNER_REWARD_STREAMS],
+    pub expiry_checkpoint_reward: u64,
+    pub expiry_checkpoint_timestamp: u64,
+    pub reward_owed: u64,
+    pub unbonding_nfts: u64,
+    pub partner_stream_generations: [u64; MAX_PARTNER_REWARD_STREAMS],
 }
 
 impl Sealed for UserBaseState {}
@@ -134,19 +487,30 @@ impl IsInitialized for UserBaseState {
 }
 
 impl Pack for UserBaseState {
-    const LEN: usize = 97;
+    const LEN: usize = 257;
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
         let src = array_ref![src, 0, UserBaseState::LEN];
         let (
             is_initialized,
+            platform,
             user,
             user_reward_ata,
             total_staked_nfts,
             total_nft_points,
             total_reward_claimed,
             reward_debt,
-        ) = array_refs![src, 1, 32, 32, 8, 8, 8, 8];
+            pending_nft_points,
+            rarity_counts,
+            set_bonus_points,
+            partner_stream_reward_debts,
+            partner_stream_owed,
+            expiry_checkpoint_reward,
+            expiry_checkpoint_timestamp,
+            reward_owed,
+            unbonding_nfts,
+            partner_stream_generations,
+        ) = array_refs![src, 1, 32, 32, 32, 8, 8, 8, 8, 8, 32, 8, 16, 16, 8, 8, 8, 8, 16];
         let is_initialized = match is_initialized {
             [0] => false,
             [1] => true,
@@ -154,12 +518,23 @@ impl Pack for UserBaseState {
         };
         Ok(UserBaseState {
             is_initialized,
+            platform: Pubkey::new_from_array(*platform),
             user: Pubkey::new_from_array(*user),
             user_reward_ata: Pubkey::new_from_array(*user_reward_ata),
             total_staked_nfts: u64::from_le_bytes(*total_staked_nfts),
             total_nft_points: u64::from_le_bytes(*total_nft_points),
             total_reward_claimed: u64::from_le_bytes(*total_reward_claimed),
             reward_debt: u64::from_le_bytes(*reward_debt),
+            pending_nft_points: u64::from_le_bytes(*pending_nft_points),
+            rarity_counts: unpack_u64_array(rarity_counts),
+            set_bonus_points: u64::from_le_bytes(*set_bonus_points),
+            partner_stream_reward_debts: unpack_u64_array(partner_stream_reward_debts),
+            partner_stream_owed: unpack_u64_array(partner_stream_owed),
+            expiry_checkpoint_reward: u64::from_le_bytes(*expiry_checkpoint_reward),
+            expiry_checkpoint_timestamp: u64::from_le_bytes(*expiry_checkpoint_timestamp),
+            reward_owed: u64::from_le_bytes(*reward_owed),
+            unbonding_nfts: u64::from_le_bytes(*unbonding_nfts),
+            partner_stream_generations: unpack_u64_array(partner_stream_generations),
         })
     }
 
@@ -167,30 +542,63 @@ impl Pack for UserBaseState {
         let dst = array_mut_ref![dst, 0, UserBaseState::LEN];
         let (
             is_initialized_dst,
+            platform_dst,
             user_dst,
             user_reward_ata_dst,
             total_staked_nft_dst,
             total_nft_points_dst,
             total_reward_claimed_dst,
             reward_debt_dst,
-        ) = mut_array_refs![dst, 1, 32, 32, 8, 8, 8, 8];
+            pending_nft_points_dst,
+            rarity_counts_dst,
+            set_bonus_points_dst,
+            partner_stream_reward_debts_dst,
+            partner_stream_owed_dst,
+            expiry_checkpoint_reward_dst,
+            expiry_checkpoint_timestamp_dst,
+            reward_owed_dst,
+            unbonding_nfts_dst,
+            partner_stream_generations_dst,
+        ) = mut_array_refs![dst, 1, 32, 32, 32, 8, 8, 8, 8, 8, 32, 8, 16, 16, 8, 8, 8, 8, 16];
         let UserBaseState {
             is_initialized,
+            platform,
             user,
             user_reward_ata,
             total_staked_nfts,
             total_nft_points,
             total_reward_claimed,
             reward_debt,
+            pending_nft_points,
+            rarity_counts,
+            set_bonus_points,
+            partner_stream_reward_debts,
+            partner_stream_owed,
+            expiry_checkpoint_reward,
+            expiry_checkpoint_timestamp,
+            reward_owed,
+            unbonding_nfts,
+            partner_stream_generations,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
+        platform_dst.copy_from_slice(platform.as_ref());
         user_dst.copy_from_slice(user.as_ref());
         user_reward_ata_dst.copy_from_slice(user_reward_ata.as_ref());
         *total_staked_nft_dst = total_staked_nfts.to_le_bytes();
         *total_nft_points_dst = total_nft_points.to_le_bytes();
         *total_reward_claimed_dst = total_reward_claimed.to_le_bytes();
         *reward_debt_dst = reward_debt.to_le_bytes();
+        *pending_nft_points_dst = pending_nft_points.to_le_bytes();
+        pack_u64_array(rarity_counts, rarity_counts_dst);
+        *set_bonus_points_dst = set_bonus_points.to_le_bytes();
+        pack_u64_array(partner_stream_reward_debts, partner_stream_reward_debts_dst);
+        pack_u64_array(partner_stream_owed, partner_stream_owed_dst);
+        *expiry_checkpoint_reward_dst = expiry_checkpoint_reward.to_le_bytes();
+        *expiry_checkpoint_timestamp_dst = expiry_checkpoint_timestamp.to_le_bytes();
+        *reward_owed_dst = reward_owed.to_le_bytes();
+        *unbonding_nfts_dst = unbonding_nfts.to_le_bytes();
+        pack_u64_array(partner_stream_generations, partner_stream_generations_dst);
     }
 }
 
@@ -201,6 +609,13 @@ pub struct UserNFTState {
     pub user_base_state: Pubkey,
     pub nft_ata: Pubkey,
     pub nft_mint: Pubkey,
+    pub nft_points: u64,
+    pub unlock_timestamp: u64,
+    pub cooldown_end_timestamp: u64,
+    pub staked_at: u64,
+    pub base_nft_points: u64,
+    pub rarity_tier: u8,
+    pub warm_up_until: u64,
 }
 
 impl Sealed for UserNFTState {}
@@ -211,12 +626,24 @@ impl IsInitialized for UserNFTState {
 }
 
 impl Pack for UserNFTState {
-    const LEN: usize = 129;
+    const LEN: usize = 178;
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
         let src = array_ref![src, 0, UserNFTState::LEN];
-        let (is_initialized, user, user_base_state, nft_ata, nft_mint) =
-            array_refs![src, 1, 32, 32, 32, 32];
+        let (
+            is_initialized,
+            user,
+            user_base_state,
+            nft_ata,
+            nft_mint,
+            nft_points,
+            unlock_timestamp,
+            cooldown_end_timestamp,
+            staked_at,
+            base_nft_points,
+            rarity_tier,
+            warm_up_until,
+        ) = array_refs![src, 1, 32, 32, 32, 32, 8, 8, 8, 8, 8, 1, 8];
         let is_initialized = match is_initialized {
             [0] => false,
             [1] => true,
@@ -228,19 +655,45 @@ impl Pack for UserNFTState {
             user_base_state: Pubkey::new_from_array(*user_base_state),
             nft_ata: Pubkey::new_from_array(*nft_ata),
             nft_mint: Pubkey::new_from_array(*nft_mint),
+            nft_points: u64::from_le_bytes(*nft_points),
+            unlock_timestamp: u64::from_le_bytes(*unlock_timestamp),
+            cooldown_end_timestamp: u64::from_le_bytes(*cooldown_end_timestamp),
+            staked_at: u64::from_le_bytes(*staked_at),
+            base_nft_points: u64::from_le_bytes(*base_nft_points),
+            rarity_tier: u8::from_le_bytes(*rarity_tier),
+            warm_up_until: u64::from_le_bytes(*warm_up_until),
         })
     }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let dst = array_mut_ref![dst, 0, UserNFTState::LEN];
-        let (is_initialized_dst, user_dst, user_base_state_dst, nft_ata_dst, nft_mint_dst) =
-            mut_array_refs![dst, 1, 32, 32, 32, 32];
+        let (
+            is_initialized_dst,
+            user_dst,
+            user_base_state_dst,
+            nft_ata_dst,
+            nft_mint_dst,
+            nft_points_dst,
+            unlock_timestamp_dst,
+            cooldown_end_timestamp_dst,
+            staked_at_dst,
+            base_nft_points_dst,
+            rarity_tier_dst,
+            warm_up_until_dst,
+        ) = mut_array_refs![dst, 1, 32, 32, 32, 32, 8, 8, 8, 8, 8, 1, 8];
         let UserNFTState {
             is_initialized,
             user,
             user_base_state,
             nft_ata,
             nft_mint,
+            nft_points,
+            unlock_timestamp,
+            cooldown_end_timestamp,
+            staked_at,
+            base_nft_points,
+            rarity_tier,
+            warm_up_until,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
@@ -248,5 +701,201 @@ impl Pack for UserNFTState {
         user_base_state_dst.copy_from_slice(user_base_state.as_ref());
         nft_ata_dst.copy_from_slice(nft_ata.as_ref());
         nft_mint_dst.copy_from_slice(nft_mint.as_ref());
+        *nft_points_dst = nft_points.to_le_bytes();
+        *unlock_timestamp_dst = unlock_timestamp.to_le_bytes();
+        *cooldown_end_timestamp_dst = cooldown_end_timestamp.to_le_bytes();
+        *staked_at_dst = staked_at.to_le_bytes();
+        *base_nft_points_dst = base_nft_points.to_le_bytes();
+        *rarity_tier_dst = rarity_tier.to_le_bytes();
+        *warm_up_until_dst = warm_up_until.to_le_bytes();
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MintPointsOverride {
+    pub is_initialized: bool,
+    pub platform: Pubkey,
+    pub nft_mint: Pubkey,
+    pub points: u64,
+}
+
+impl Sealed for MintPointsOverride {}
+impl IsInitialized for MintPointsOverride {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for MintPointsOverride {
+    const LEN: usize = 73;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, MintPointsOverride::LEN];
+        let (is_initialized, platform, nft_mint, points) = array_refs![src, 1, 32, 32, 8];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(MintPointsOverride {
+            is_initialized,
+            platform: Pubkey::new_from_array(*platform),
+            nft_mint: Pubkey::new_from_array(*nft_mint),
+            points: u64::from_le_bytes(*points),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, MintPointsOverride::LEN];
+        let (is_initialized_dst, platform_dst, nft_mint_dst, points_dst) =
+            mut_array_refs![dst, 1, 32, 32, 8];
+        let MintPointsOverride {
+            is_initialized,
+            platform,
+            nft_mint,
+            points,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        platform_dst.copy_from_slice(platform.as_ref());
+        nft_mint_dst.copy_from_slice(nft_mint.as_ref());
+        *points_dst = points.to_le_bytes();
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MintListEntry {
+    pub is_initialized: bool,
+    pub platform: Pubkey,
+    pub nft_mint: Pubkey,
+    pub list: u8,
+}
+
+impl Sealed for MintListEntry {}
+impl IsInitialized for MintListEntry {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for MintListEntry {
+    const LEN: usize = 66;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, MintListEntry::LEN];
+        let (is_initialized, platform, nft_mint, list) = array_refs![src, 1, 32, 32, 1];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(MintListEntry {
+            is_initialized,
+            platform: Pubkey::new_from_array(*platform),
+            nft_mint: Pubkey::new_from_array(*nft_mint),
+            list: u8::from_le_bytes(*list),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, MintListEntry::LEN];
+        let (is_initialized_dst, platform_dst, nft_mint_dst, list_dst) =
+            mut_array_refs![dst, 1, 32, 32, 1];
+        let MintListEntry {
+            is_initialized,
+            platform,
+            nft_mint,
+            list,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        platform_dst.copy_from_slice(platform.as_ref());
+        nft_mint_dst.copy_from_slice(nft_mint.as_ref());
+        *list_dst = list.to_le_bytes();
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct VestingSchedule {
+    pub is_initialized: bool,
+    pub platform: Pubkey,
+    pub user: Pubkey,
+    pub locked_amount: u64,
+    pub unlocked_amount: u64,
+    pub total_withdrawn: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+}
+
+impl Sealed for VestingSchedule {}
+impl IsInitialized for VestingSchedule {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for VestingSchedule {
+    const LEN: usize = 105;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, VestingSchedule::LEN];
+        let (
+            is_initialized,
+            platform,
+            user,
+            locked_amount,
+            unlocked_amount,
+            total_withdrawn,
+            start_timestamp,
+            end_timestamp,
+        ) = array_refs![src, 1, 32, 32, 8, 8, 8, 8, 8];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(VestingSchedule {
+            is_initialized,
+            platform: Pubkey::new_from_array(*platform),
+            user: Pubkey::new_from_array(*user),
+            locked_amount: u64::from_le_bytes(*locked_amount),
+            unlocked_amount: u64::from_le_bytes(*unlocked_amount),
+            total_withdrawn: u64::from_le_bytes(*total_withdrawn),
+            start_timestamp: u64::from_le_bytes(*start_timestamp),
+            end_timestamp: u64::from_le_bytes(*end_timestamp),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, VestingSchedule::LEN];
+        let (
+            is_initialized_dst,
+            platform_dst,
+            user_dst,
+            locked_amount_dst,
+            unlocked_amount_dst,
+            total_withdrawn_dst,
+            start_timestamp_dst,
+            end_timestamp_dst,
+        ) = mut_array_refs![dst, 1, 32, 32, 8, 8, 8, 8, 8];
+        let VestingSchedule {
+            is_initialized,
+            platform,
+            user,
+            locked_amount,
+            unlocked_amount,
+            total_withdrawn,
+            start_timestamp,
+            end_timestamp,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        platform_dst.copy_from_slice(platform.as_ref());
+        user_dst.copy_from_slice(user.as_ref());
+        *locked_amount_dst = locked_amount.to_le_bytes();
+        *unlocked_amount_dst = unlocked_amount.to_le_bytes();
+        *total_withdrawn_dst = total_withdrawn.to_le_bytes();
+        *start_timestamp_dst = start_timestamp.to_le_bytes();
+        *end_timestamp_dst = end_timestamp.to_le_bytes();
     }
 }
@@ -1,9 +1,13 @@
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+    account_info::AccountInfo, hash::hashv, program_error::ProgramError, program_pack::Pack,
+    pubkey::Pubkey, rent::Rent, system_program, sysvar,
 };
 use spl_token;
 
-use crate::error::NFTStakingContractError;
+use crate::{
+    error::NFTStakingContractError,
+    state::{LOCK_TIERS, MAX_LOCK_TIER_MULTIPLIER, MAX_LOYALTY_BONUS_PERCENTAGE},
+};
 
 pub mod admin {
     solana_program::declare_id!("J7A8AeFaPNxe3w7jCxnE2xHVWZz2GgjAF9LWky5AG2Jq");
@@ -53,11 +57,39 @@ impl Validator {
 
         let token_mint_unpacked = spl_token::state::Mint::unpack(&token_mint.try_borrow_data()?)?;
 
-        if token_mint_unpacked.decimals != 8
-            || token_ata_unpacked.mint != reward_mint::id()
-            || *token_ata.owner != spl_token::ID
-        {
-            return Err(NFTStakingContractError::InvalidTokenATA.into());
+        if *token_ata.owner != spl_token::ID {
+            return Err(NFTStakingContractError::TokenAccountNotOwnedByTokenProgram.into());
+        }
+
+        if token_mint_unpacked.decimals != 8 {
+            return Err(NFTStakingContractError::InvalidRewardMintDecimals.into());
+        }
+
+        if token_ata_unpacked.mint != reward_mint::id() {
+            return Err(NFTStakingContractError::WrongRewardMint.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_token_account(
+        token_account: &AccountInfo,
+        token_mint: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if *token_account.owner != spl_token::ID {
+            return Err(NFTStakingContractError::TokenAccountNotOwnedByTokenProgram.into());
+        }
+
+        let token_account_unpacked =
+            spl_token::state::Account::unpack(&token_account.try_borrow_data()?)?;
+
+        if token_account_unpacked.mint != *token_mint {
+            return Err(NFTStakingContractError::WrongRewardMint.into());
+        }
+
+        if token_account_unpacked.owner != *owner {
+            return Err(NFTStakingContractError::IncorrectATAOwner.into());
         }
 
         Ok(())
@@ -71,28 +103,89 @@ impl Validator {
 
         let token_mint_unpacked = spl_token::state::Mint::unpack(&token_mint.try_borrow_data()?)?;
 
-        if token_ata_unpacked.amount != 1
-            || token_mint_unpacked.decimals != 0
-            || token_ata_unpacked.mint != *token_mint.key
-            || *token_ata.owner != spl_token::ID
-        {
-            return Err(NFTStakingContractError::InvalidTokenATA.into());
+        if *token_ata.owner != spl_token::ID {
+            return Err(NFTStakingContractError::TokenAccountNotOwnedByTokenProgram.into());
+        }
+
+        if token_ata_unpacked.amount != 1 {
+            return Err(NFTStakingContractError::NftAmountNotOne.into());
+        }
+
+        if token_mint_unpacked.decimals != 0 {
+            return Err(NFTStakingContractError::NftDecimalsNotZero.into());
+        }
+
+        if token_ata_unpacked.mint != *token_mint.key {
+            return Err(NFTStakingContractError::NftMintMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_nft_mint(token_mint: &AccountInfo) -> Result<(), ProgramError> {
+        if *token_mint.owner != spl_token::ID {
+            return Err(NFTStakingContractError::TokenAccountNotOwnedByTokenProgram.into());
+        }
+
+        let token_mint_unpacked = spl_token::state::Mint::unpack(&token_mint.try_borrow_data()?)?;
+
+        if token_mint_unpacked.decimals != 0 {
+            return Err(NFTStakingContractError::NftDecimalsNotZero.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn rarity_merkle_leaf(nft_mint: &Pubkey, points: u64, tier: u8) -> [u8; 32] {
+        hashv(&[&[0], nft_mint.as_ref(), &points.to_le_bytes(), &[tier]]).to_bytes()
+    }
+
+    pub fn validate_rarity_proof(
+        root: &[u8; 32],
+        nft_mint: &Pubkey,
+        points: u64,
+        tier: u8,
+        proof: &[[u8; 32]],
+    ) -> Result<(), ProgramError> {
+        let computed_root = proof.iter().fold(
+            Self::rarity_merkle_leaf(nft_mint, points, tier),
+            |node, sibling| {
+                let (left, right) = if node <= *sibling {
+                    (node, *sibling)
+                } else {
+                    (*sibling, node)
+                };
+
+                hashv(&[&[1], &left, &right]).to_bytes()
+            },
+        );
+
+        if computed_root != *root {
+            return Err(NFTStakingContractError::InvalidRarityProof.into());
         }
 
         Ok(())
     }
 
-    pub fn validate_equality(lt: Pubkey, rt: Pubkey) -> Result<(), ProgramError> {
+    pub fn validate_equality(
+        lt: Pubkey,
+        rt: Pubkey,
+        error: NFTStakingContractError,
+    ) -> Result<(), ProgramError> {
         if lt != rt {
-            return Err(NFTStakingContractError::EqualityMismatch.into());
+            return Err(error.into());
         }
 
         Ok(())
     }
 
-    pub fn validate_bool(lt: bool, rt: bool) -> Result<(), ProgramError> {
+    pub fn validate_bool(
+        lt: bool,
+        rt: bool,
+        error: NFTStakingContractError,
+    ) -> Result<(), ProgramError> {
         if lt != rt {
-            return Err(NFTStakingContractError::BoolEqualityMismatch.into());
+            return Err(error.into());
         }
 
         Ok(())
@@ -102,8 +195,96 @@ impl Validator {
         state_account: &AccountInfo,
         program_id: Pubkey,
     ) -> Result<(), ProgramError> {
-        if *state_account.owner != program_id || state_account.data_is_empty() {
-            return Err(NFTStakingContractError::InvalidStateAccount.into());
+        if *state_account.owner != program_id {
+            return Err(NFTStakingContractError::StateAccountNotOwnedByProgram.into());
+        }
+
+        if state_account.data_is_empty() {
+            return Err(NFTStakingContractError::StateAccountNotInitialized.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_rent_exempt(
+        state_account: &AccountInfo,
+        rent: &Rent,
+    ) -> Result<(), ProgramError> {
+        if !rent.is_exempt(state_account.lamports(), state_account.data_len()) {
+            return Err(NFTStakingContractError::StateAccountNotRentExempt.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_lock_tiers(
+        durations_in_sec: &[u64; LOCK_TIERS],
+        multipliers: &[u16; LOCK_TIERS],
+    ) -> Result<(), ProgramError> {
+        if durations_in_sec[0] != 0
+            || durations_in_sec.windows(2).any(|pair| pair[0] >= pair[1])
+            || multipliers
+                .iter()
+                .any(|multiplier| *multiplier < 100 || *multiplier > MAX_LOCK_TIER_MULTIPLIER)
+        {
+            return Err(NFTStakingContractError::InvalidLockTiers.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_loyalty_curve(
+        step_in_sec: u64,
+        step_percentage: u16,
+        max_percentage: u16,
+    ) -> Result<(), ProgramError> {
+        if (step_percentage > 0 && step_in_sec == 0)
+            || step_percentage > max_percentage
+            || max_percentage > MAX_LOYALTY_BONUS_PERCENTAGE
+        {
+            return Err(NFTStakingContractError::InvalidLoyaltyCurve.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_token_program(token_program: &AccountInfo) -> Result<(), ProgramError> {
+        if *token_program.key != spl_token::id() {
+            return Err(NFTStakingContractError::InvalidTokenProgram.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_system_program(system_program: &AccountInfo) -> Result<(), ProgramError> {
+        if *system_program.key != system_program::id() {
+            return Err(NFTStakingContractError::InvalidSystemProgram.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_associated_token_program(
+        associated_token_program: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if *associated_token_program.key != spl_associated_token_account::id() {
+            return Err(NFTStakingContractError::InvalidAssociatedTokenProgram.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_metadata_program(metadata_program: &AccountInfo) -> Result<(), ProgramError> {
+        if *metadata_program.key != metaplex_token_metadata::id() {
+            return Err(NFTStakingContractError::InvalidMetadataProgram.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_rent_sysvar(rent_sysvar: &AccountInfo) -> Result<(), ProgramError> {
+        if *rent_sysvar.key != sysvar::rent::id() {
+            return Err(NFTStakingContractError::InvalidRentSysvar.into());
         }
 
         Ok(())
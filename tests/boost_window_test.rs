@@ -0,0 +1,101 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::error::NFTStakingContractError;
+    use solana_program::program_error::ProgramError;
+
+    const BOOST_MULTIPLIER: u16 = 200;
+
+    // One RA NFT alone in the default emission earns this many reward units
+    // per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    fn add_boost_window(
+        context: &mut TestContext,
+        pool: &Pool,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> Result<(), ProgramError> {
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(
+                    32,
+                    &[
+                        &start_timestamp.to_le_bytes(),
+                        &end_timestamp.to_le_bytes(),
+                        &BOOST_MULTIPLIER.to_le_bytes(),
+                    ],
+                ),
+                vec![],
+            )
+            .map(|_| ())
+    }
+
+    #[test]
+    fn boost_window_multiplies_overlapping_emission_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let now = context.now() as u64;
+
+        add_boost_window(&mut context, &pool, now + 10, now + 20).unwrap();
+
+        assert_eq!(
+            add_boost_window(&mut context, &pool, now + 15, now + 25),
+            Err(NFTStakingContractError::BoostWindowOverlap.into())
+        );
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        // Ten plain seconds and the first half of the window.
+        context.warp(15);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            20 * REWARD_PER_SEC * TOKENS_PER_REWARD
+        );
+
+        // The second half of the window and ten plain seconds.
+        context.warp(15);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            40 * REWARD_PER_SEC * TOKENS_PER_REWARD
+        );
+    }
+
+    #[test]
+    fn removed_boost_window_stops_boosting_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let now = context.now() as u64;
+
+        add_boost_window(&mut context, &pool, now, now + 20).unwrap();
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context
+            .admin_instruction(&pool, instruction_data(33, &[&[0]]), vec![])
+            .unwrap();
+
+        assert_eq!(context.platform_state(&pool).boost_window_count, 0);
+
+        context.warp(10);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        // The boost accrued before the removal is kept.
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            30 * REWARD_PER_SEC * TOKENS_PER_REWARD
+        );
+    }
+}
@@ -0,0 +1,122 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Nft, Pool, TestContext};
+    use nft_staking_contract::{error::NFTStakingContractError, state::MintPointsOverride};
+    use solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        rent::Rent,
+        system_instruction, system_program,
+    };
+
+    fn remove_mint_points_override_ix(
+        context: &TestContext,
+        pool: &Pool,
+        nft: &Nft,
+    ) -> Instruction {
+        Instruction {
+            program_id: context.program_id,
+            accounts: vec![
+                AccountMeta::new(nft_staking_contract::validation::admin::id(), true),
+                AccountMeta::new(pool.platform, false),
+                AccountMeta::new_readonly(nft.mint, false),
+                AccountMeta::new(context.mint_points_override_address(pool, &nft.mint), false),
+            ],
+            data: vec![14],
+        }
+    }
+
+    fn setup() -> (TestContext, Pool, Nft) {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let mint_points_override = context.mint_points_override_address(&pool, &nft.mint);
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(12, &[&500u64.to_le_bytes()]),
+                vec![
+                    AccountMeta::new_readonly(nft.mint, false),
+                    AccountMeta::new(mint_points_override, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            )
+            .unwrap();
+
+        (context, pool, nft)
+    }
+
+    #[test]
+    fn close_state_account_purged_test() {
+        let (mut context, pool, nft) = setup();
+        let mint_points_override = context.mint_points_override_address(&pool, &nft.mint);
+        let admin_lamports = context.lamports(&nft_staking_contract::validation::admin::id());
+        let override_lamports = context.lamports(&mint_points_override);
+
+        let remove_ix = remove_mint_points_override_ix(&context, &pool, &nft);
+        context.process_transaction(&[remove_ix]).unwrap();
+
+        assert!(!context.exists(&mint_points_override));
+        assert_eq!(
+            context.lamports(&nft_staking_contract::validation::admin::id()),
+            admin_lamports + override_lamports
+        );
+    }
+
+    #[test]
+    fn close_state_account_revival_test() {
+        let (mut context, pool, nft) = setup();
+        let mint_points_override = context.mint_points_override_address(&pool, &nft.mint);
+        let admin = nft_staking_contract::validation::admin::id();
+
+        let remove_ix = remove_mint_points_override_ix(&context, &pool, &nft);
+        let revive_ix = system_instruction::transfer(
+            &admin,
+            &mint_points_override,
+            Rent::default().minimum_balance(MintPointsOverride::LEN),
+        );
+        let update_ix = Instruction {
+            program_id: context.program_id,
+            accounts: vec![
+                AccountMeta::new(admin, true),
+                AccountMeta::new(pool.platform, false),
+                AccountMeta::new_readonly(nft.mint, false),
+                AccountMeta::new(mint_points_override, false),
+            ],
+            data: instruction_data(13, &[&700u64.to_le_bytes()]),
+        };
+
+        assert_eq!(
+            context.process_transaction(&[remove_ix.clone(), revive_ix.clone(), update_ix]),
+            Err(NFTStakingContractError::StateAccountNotOwnedByProgram.into())
+        );
+        assert_eq!(
+            context.process_transaction(&[remove_ix.clone(), revive_ix.clone(), remove_ix]),
+            Err(NFTStakingContractError::StateAccountNotOwnedByProgram.into())
+        );
+
+        assert_eq!(
+            MintPointsOverride::unpack(&context.account(&mint_points_override).data)
+                .unwrap()
+                .points,
+            500
+        );
+
+        let remove_ix = remove_mint_points_override_ix(&context, &pool, &nft);
+        context
+            .process_transaction(&[remove_ix, revive_ix])
+            .unwrap();
+
+        let revived_account = context.account(&mint_points_override);
+        assert_eq!(revived_account.owner, system_program::id());
+        assert!(revived_account.data.iter().all(|byte| *byte == 0));
+        assert_eq!(
+            MintPointsOverride::unpack(&revived_account.data),
+            Err(solana_program::program_error::ProgramError::UninitializedAccount)
+        );
+    }
+}
@@ -0,0 +1,996 @@
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use metaplex_token_metadata::state::{Data, Key, Metadata, MAX_METADATA_LEN};
+use nft_staking_contract::{
+    events::NFTStakingContractEvent,
+    processor::Processor,
+    state::{PlatformState, UserBaseState, UserNFTState, VestingSchedule, DEFAULT_POOL_NAME},
+    validation::{admin, reward_mint},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::Once,
+};
+
+pub const START_TIMESTAMP: i64 = 1_700_000_000;
+
+pub const USER_LAMPORTS: u64 = 100_000_000_000;
+
+pub const VAULT_DEPOSIT: u64 = 100000000000000000;
+
+thread_local! {
+    static CLOCK: Cell<i64> = const { Cell::new(START_TIMESTAMP) };
+    static PROGRAM_ID: Cell<Pubkey> = const { Cell::new(Pubkey::new_from_array([0; 32])) };
+    static EVENTS: RefCell<Vec<NFTStakingContractEvent>> = const { RefCell::new(Vec::new()) };
+}
+
+struct TestSyscallStubs;
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        for field in fields {
+            if let Ok(event) = NFTStakingContractEvent::try_from_slice(field) {
+                EVENTS.with(|events| events.borrow_mut().push(event));
+            }
+        }
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: CLOCK.with(|clock| clock.get()),
+            ..Clock::default()
+        };
+
+        unsafe { *(var_addr as *mut Clock) = clock };
+
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = PROGRAM_ID.with(|program_id| program_id.get());
+
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut callee_account_infos = Vec::new();
+
+        for meta in &instruction.accounts {
+            let mut account_info = account_infos
+                .iter()
+                .find(|account_info| *account_info.key == meta.pubkey)
+                .unwrap_or_else(|| panic!("account {} not passed to invoke", meta.pubkey))
+                .clone();
+
+            if meta.is_signer && !account_info.is_signer && !signers.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            // The runtime merges the privileges of duplicate account metas.
+            account_info.is_signer = instruction
+                .accounts
+                .iter()
+                .any(|other| other.pubkey == meta.pubkey && other.is_signer);
+            account_info.is_writable = instruction
+                .accounts
+                .iter()
+                .any(|other| other.pubkey == meta.pubkey && other.is_writable);
+
+            callee_account_infos.push(account_info);
+        }
+
+        if instruction.program_id == spl_token::id() {
+            spl_token::processor::Processor::process(
+                &instruction.program_id,
+                &callee_account_infos,
+                &instruction.data,
+            )
+        } else if instruction.program_id == system_program::id() {
+            process_system_instruction(&callee_account_infos, &instruction.data)
+        } else {
+            panic!("unexpected invoke of {}", instruction.program_id)
+        }
+    }
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new(&data[offset..offset + 32])
+}
+
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !from.data_is_empty() || from.lamports() < lamports {
+        return Err(ProgramError::Custom(1));
+    }
+
+    **from.lamports.borrow_mut() -= lamports;
+    **to.lamports.borrow_mut() += lamports;
+
+    Ok(())
+}
+
+fn create_account(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    if to.lamports() > 0 || !to.data_is_empty() {
+        return Err(ProgramError::Custom(0));
+    }
+
+    transfer_lamports(from, to, lamports)?;
+
+    *to.data.borrow_mut() = Box::leak(vec![0; space as usize].into_boxed_slice());
+    to.assign(owner);
+
+    Ok(())
+}
+
+fn process_system_instruction(account_infos: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match u32::from_le_bytes(data[0..4].try_into().unwrap()) {
+        0 => {
+            if !account_infos[1].is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            create_account(
+                &account_infos[0],
+                &account_infos[1],
+                read_u64(data, 4),
+                read_u64(data, 12),
+                &read_pubkey(data, 20),
+            )
+        }
+        2 => transfer_lamports(&account_infos[0], &account_infos[1], read_u64(data, 4)),
+        3 => {
+            let base = read_pubkey(data, 4);
+            let seed_len = read_u64(data, 36) as usize;
+            let seed = std::str::from_utf8(&data[44..44 + seed_len]).unwrap();
+            let offset = 44 + seed_len;
+            let owner = read_pubkey(data, offset + 16);
+
+            if Pubkey::create_with_seed(&base, seed, &owner).unwrap() != *account_infos[1].key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+
+            create_account(
+                &account_infos[0],
+                &account_infos[1],
+                read_u64(data, offset),
+                read_u64(data, offset + 8),
+                &owner,
+            )
+        }
+        tag => panic!("unsupported system instruction {}", tag),
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+pub struct Pool {
+    pub platform: Pubkey,
+    pub pda: Pubkey,
+    pub vault: Pubkey,
+}
+
+pub struct Staker {
+    pub user: Pubkey,
+    pub base_state: Pubkey,
+    pub reward_ata: Pubkey,
+}
+
+pub struct Nft {
+    pub mint: Pubkey,
+    pub ata: Pubkey,
+    pub metadata: Pubkey,
+    pub state: Pubkey,
+}
+
+pub struct TestContext {
+    pub program_id: Pubkey,
+    pub accounts: HashMap<Pubkey, TestAccount>,
+    pub events: Vec<NFTStakingContractEvent>,
+    pub admin_reward_ata: Pubkey,
+}
+
+impl TestContext {
+    pub fn new() -> Self {
+        static INIT: Once = Once::new();
+
+        INIT.call_once(|| {
+            set_syscall_stubs(Box::new(TestSyscallStubs));
+        });
+
+        CLOCK.with(|clock| clock.set(START_TIMESTAMP));
+
+        let mut context = TestContext {
+            program_id: Pubkey::new_unique(),
+            accounts: HashMap::new(),
+            events: Vec::new(),
+            admin_reward_ata: get_associated_token_address(&admin::id(), &reward_mint::id()),
+        };
+
+        context.set_lamports(&admin::id(), USER_LAMPORTS);
+        context.set_mint(&reward_mint::id(), Some(admin::id()), 8, VAULT_DEPOSIT);
+        context.set_token_account(
+            &context.admin_reward_ata.clone(),
+            &reward_mint::id(),
+            &admin::id(),
+            VAULT_DEPOSIT,
+        );
+
+        context
+    }
+
+    pub fn now(&self) -> i64 {
+        CLOCK.with(|clock| clock.get())
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        CLOCK.with(|clock| clock.set(clock.get() + seconds));
+    }
+
+    pub fn account(&self, key: &Pubkey) -> TestAccount {
+        self.accounts.get(key).cloned().unwrap_or_default()
+    }
+
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.accounts.contains_key(key)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).lamports
+    }
+
+    pub fn set_lamports(&mut self, key: &Pubkey, lamports: u64) {
+        self.accounts.entry(*key).or_default().lamports = lamports;
+    }
+
+    pub fn set_account(&mut self, key: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+        let lamports = Rent::default().minimum_balance(data.len());
+
+        self.accounts.insert(
+            *key,
+            TestAccount {
+                lamports,
+                data,
+                owner: *owner,
+            },
+        );
+    }
+
+    pub fn set_mint(&mut self, key: &Pubkey, authority: Option<Pubkey>, decimals: u8, supply: u64) {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+
+        spl_token::state::Mint::pack(
+            spl_token::state::Mint {
+                mint_authority: authority.into(),
+                supply,
+                decimals,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+
+        self.set_account(key, &spl_token::id(), data);
+    }
+
+    pub fn set_token_account(&mut self, key: &Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+
+        spl_token::state::Account::pack(
+            spl_token::state::Account {
+                mint: *mint,
+                owner: *owner,
+                amount,
+                state: spl_token::state::AccountState::Initialized,
+                ..spl_token::state::Account::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+
+        self.set_account(key, &spl_token::id(), data);
+    }
+
+    pub fn token_account(&self, key: &Pubkey) -> spl_token::state::Account {
+        spl_token::state::Account::unpack(&self.account(key).data).unwrap()
+    }
+
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        self.token_account(key).amount
+    }
+
+    pub fn mint_supply(&self, key: &Pubkey) -> u64 {
+        spl_token::state::Mint::unpack(&self.account(key).data)
+            .unwrap()
+            .supply
+    }
+
+    pub fn platform_state(&self, pool: &Pool) -> PlatformState {
+        PlatformState::unpack(&self.account(&pool.platform).data).unwrap()
+    }
+
+    pub fn base_state(&self, staker: &Staker) -> UserBaseState {
+        UserBaseState::unpack(&self.account(&staker.base_state).data).unwrap()
+    }
+
+    pub fn nft_state(&self, nft: &Nft) -> UserNFTState {
+        UserNFTState::unpack(&self.account(&nft.state).data).unwrap()
+    }
+
+    pub fn vesting_schedule(&self, pool: &Pool, staker: &Staker) -> VestingSchedule {
+        VestingSchedule::unpack(
+            &self
+                .account(&self.vesting_schedule_address(pool, staker))
+                .data,
+        )
+        .unwrap()
+    }
+
+    pub fn process(
+        &mut self,
+        instruction_data: Vec<u8>,
+        metas: Vec<AccountMeta>,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: metas,
+            data: instruction_data,
+        };
+
+        self.process_transaction(&[instruction])
+    }
+
+    /// Executes the instructions atomically, purging accounts left without
+    /// lamports once the whole transaction succeeded, like the runtime does.
+    pub fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let mut accounts = self.accounts.clone();
+        let mut events = Vec::new();
+
+        for instruction in instructions {
+            events.extend(Self::execute(&self.program_id, &mut accounts, instruction)?);
+        }
+
+        accounts.retain(|_, account| account.lamports > 0);
+
+        self.accounts = accounts;
+        self.events.extend(events.iter().cloned());
+
+        Ok(events)
+    }
+
+    fn execute(
+        program_id: &Pubkey,
+        accounts: &mut HashMap<Pubkey, TestAccount>,
+        instruction: &Instruction,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let metas = &instruction.accounts;
+        let mut keys: Vec<Pubkey> = Vec::new();
+
+        for meta in metas {
+            if !keys.contains(&meta.pubkey) {
+                keys.push(meta.pubkey);
+            }
+        }
+
+        let account = |key: &Pubkey| accounts.get(key).cloned().unwrap_or_default();
+
+        let mut lamports: Vec<u64> = keys.iter().map(|key| account(key).lamports).collect();
+        let mut data: Vec<Vec<u8>> = keys.iter().map(|key| account(key).data).collect();
+        let owners: Vec<Pubkey> = keys.iter().map(|key| account(key).owner).collect();
+
+        let unique_account_infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .zip(owners.iter())
+            .map(|(((key, lamports), data), owner)| {
+                let is_signer = metas
+                    .iter()
+                    .any(|meta| meta.pubkey == *key && meta.is_signer);
+                let is_writable = metas
+                    .iter()
+                    .any(|meta| meta.pubkey == *key && meta.is_writable);
+
+                AccountInfo::new(key, is_signer, is_writable, lamports, data, owner, false, 0)
+            })
+            .collect();
+
+        let account_infos: Vec<AccountInfo> = metas
+            .iter()
+            .map(|meta| {
+                let index = keys.iter().position(|key| *key == meta.pubkey).unwrap();
+                let mut account_info = unique_account_infos[index].clone();
+                account_info.is_signer = meta.is_signer;
+                account_info.is_writable = meta.is_writable;
+                account_info
+            })
+            .collect();
+
+        PROGRAM_ID.with(|current_program_id| current_program_id.set(*program_id));
+        EVENTS.with(|events| events.borrow_mut().clear());
+
+        let result = if instruction.program_id == *program_id {
+            Processor::unpack_and_process_instruction(program_id, &account_infos, &instruction.data)
+        } else if instruction.program_id == system_program::id() {
+            process_system_instruction(&account_infos, &instruction.data)
+        } else {
+            panic!("unexpected instruction for {}", instruction.program_id)
+        };
+
+        let events = EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()));
+
+        result?;
+
+        let updated_accounts: Vec<(Pubkey, TestAccount)> = unique_account_infos
+            .iter()
+            .map(|account_info| {
+                (
+                    *account_info.key,
+                    TestAccount {
+                        lamports: account_info.lamports(),
+                        data: account_info.data.borrow().to_vec(),
+                        owner: unsafe { std::ptr::read_volatile(account_info.owner) },
+                    },
+                )
+            })
+            .collect();
+
+        drop(account_infos);
+        drop(unique_account_infos);
+
+        accounts.extend(updated_accounts);
+
+        Ok(events)
+    }
+
+    pub fn platform_address(&self, pool_name: &str) -> Pubkey {
+        let seed = if pool_name.is_empty() {
+            DEFAULT_POOL_NAME
+        } else {
+            pool_name
+        };
+
+        Pubkey::create_with_seed(&admin::id(), seed, &self.program_id).unwrap()
+    }
+
+    pub fn pda_address(&self, platform: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &["nft_staking_contract".as_bytes(), platform.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn user_base_state_address(&self, platform: &Pubkey, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                "user_base_state".as_bytes(),
+                platform.as_ref(),
+                user.as_ref(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn user_nft_state_address(&self, platform: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                "user_nft_state".as_bytes(),
+                platform.as_ref(),
+                nft_mint.as_ref(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn sol_treasury_address(&self, pool: &Pool) -> Pubkey {
+        Pubkey::find_program_address(
+            &["sol_treasury".as_bytes(), pool.platform.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn vesting_schedule_address(&self, pool: &Pool, staker: &Staker) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                "vesting_schedule".as_bytes(),
+                pool.platform.as_ref(),
+                staker.user.as_ref(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn mint_points_override_address(&self, pool: &Pool, nft_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                "mint_points_override".as_bytes(),
+                pool.platform.as_ref(),
+                nft_mint.as_ref(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn mint_list_entry_address(&self, pool: &Pool, nft_mint: &Pubkey, list: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                "mint_list".as_bytes(),
+                &[list],
+                pool.platform.as_ref(),
+                nft_mint.as_ref(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    pub fn initialize_platform(
+        &mut self,
+        pool_name: &str,
+        percent: u8,
+        distribution: u64,
+        accumulation: u64,
+        reward_mint_cap: u64,
+    ) -> Result<Pool, ProgramError> {
+        let platform = self.platform_address(pool_name);
+        let pda = self.pda_address(&platform);
+        let vault = get_associated_token_address(&pda, &reward_mint::id());
+
+        if !self.exists(&vault) {
+            self.set_token_account(&vault, &reward_mint::id(), &pda, 0);
+        }
+
+        if reward_mint_cap > 0 {
+            self.set_mint(&reward_mint::id(), Some(pda), 8, VAULT_DEPOSIT);
+        }
+
+        let mut instruction_data = vec![0, percent];
+        instruction_data.extend_from_slice(&distribution.to_le_bytes());
+        instruction_data.extend_from_slice(&accumulation.to_le_bytes());
+        instruction_data.push(pool_name.len() as u8);
+        instruction_data.extend_from_slice(pool_name.as_bytes());
+        instruction_data.extend_from_slice(&reward_mint_cap.to_le_bytes());
+
+        self.process(
+            instruction_data,
+            vec![
+                AccountMeta::new(admin::id(), true),
+                AccountMeta::new(platform, false),
+                AccountMeta::new_readonly(reward_mint::id(), false),
+                AccountMeta::new(self.admin_reward_ata, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )?;
+
+        Ok(Pool {
+            platform,
+            pda,
+            vault,
+        })
+    }
+
+    /// Vault-funded pool emitting 10% of the supply over 1000 seconds, one
+    /// accumulation period per second.
+    pub fn default_pool(&mut self) -> Pool {
+        self.initialize_platform("", 10, 1000, 1, 0).unwrap()
+    }
+
+    pub fn create_staker(&mut self, pool: &Pool) -> Staker {
+        let user = Pubkey::new_unique();
+        let reward_ata = get_associated_token_address(&user, &reward_mint::id());
+
+        self.set_lamports(&user, USER_LAMPORTS);
+        self.set_token_account(&reward_ata, &reward_mint::id(), &user, 0);
+
+        Staker {
+            user,
+            base_state: self.user_base_state_address(&pool.platform, &user),
+            reward_ata,
+        }
+    }
+
+    pub fn create_nft(&mut self, pool: &Pool, staker: &Staker, name: &str) -> Nft {
+        let mint = Pubkey::new_unique();
+        let ata = get_associated_token_address(&staker.user, &mint);
+        let metadata_program = metaplex_token_metadata::id();
+        let metadata = Pubkey::find_program_address(
+            &[
+                "metadata".as_bytes(),
+                metadata_program.as_ref(),
+                mint.as_ref(),
+            ],
+            &metadata_program,
+        )
+        .0;
+
+        self.set_mint(&mint, None, 0, 1);
+        self.set_token_account(&ata, &mint, &staker.user, 1);
+
+        let mut metadata_data = Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint,
+            data: Data {
+                name: name.to_string(),
+                symbol: String::new(),
+                uri: String::new(),
+                seller_fee_basis_points: 0,
+                creators: None,
+            },
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        metadata_data.resize(MAX_METADATA_LEN, 0);
+
+        self.set_account(&metadata, &metadata_program, metadata_data);
+
+        Nft {
+            mint,
+            ata,
+            metadata,
+            state: self.user_nft_state_address(&pool.platform, &mint),
+        }
+    }
+
+    pub fn staked_nft_states(&self, staker: &Staker) -> Vec<Pubkey> {
+        let mut nft_states: Vec<Pubkey> = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                account.owner == self.program_id && account.data.len() == UserNFTState::LEN
+            })
+            .filter_map(|(key, account)| {
+                UserNFTState::unpack(&account.data)
+                    .ok()
+                    .filter(|nft_state| {
+                        nft_state.user_base_state == staker.base_state
+                            && nft_state.cooldown_end_timestamp == 0
+                    })
+                    .map(|_| *key)
+            })
+            .collect();
+
+        nft_states.sort();
+        nft_states
+    }
+
+    fn protocol_fee_accounts(&self, pool: &Pool, metas: &mut Vec<AccountMeta>) {
+        let platform_state = self.platform_state(pool);
+
+        if platform_state.protocol_fee_bps > 0 {
+            metas.push(AccountMeta::new(
+                platform_state.protocol_fee_treasury_ata,
+                false,
+            ));
+        }
+    }
+
+    fn vesting_accounts(&self, pool: &Pool, staker: &Staker, metas: &mut Vec<AccountMeta>) {
+        if self.platform_state(pool).vesting_period_in_sec > 0 {
+            metas.push(AccountMeta::new(
+                self.vesting_schedule_address(pool, staker),
+                false,
+            ));
+            metas.push(AccountMeta::new_readonly(system_program::id(), false));
+        }
+    }
+
+    fn loyalty_accounts(
+        &self,
+        staker: &Staker,
+        skipped: Option<&Pubkey>,
+        metas: &mut Vec<AccountMeta>,
+    ) {
+        for nft_state in self.staked_nft_states(staker) {
+            if skipped != Some(&nft_state) {
+                metas.push(AccountMeta::new(nft_state, false));
+            }
+        }
+    }
+
+    pub fn stake(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        nft: &Nft,
+        lock_duration_in_sec: u64,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let mut instruction_data = vec![1];
+        instruction_data.extend_from_slice(&lock_duration_in_sec.to_le_bytes());
+
+        let metas = self.stake_metas(pool, staker, nft);
+
+        self.process(instruction_data, metas)
+    }
+
+    pub fn stake_metas(&self, pool: &Pool, staker: &Staker, nft: &Nft) -> Vec<AccountMeta> {
+        let platform_state = self.platform_state(pool);
+
+        let mut metas = vec![
+            AccountMeta::new(staker.user, true),
+            AccountMeta::new_readonly(admin::id(), true),
+            AccountMeta::new(pool.platform, false),
+            AccountMeta::new(staker.base_state, false),
+            AccountMeta::new(nft.state, false),
+            AccountMeta::new(nft.ata, false),
+            AccountMeta::new_readonly(nft.mint, false),
+            AccountMeta::new(reward_mint::id(), false),
+            AccountMeta::new(staker.reward_ata, false),
+            AccountMeta::new(pool.vault, false),
+            AccountMeta::new_readonly(pool.pda, false),
+            AccountMeta::new_readonly(nft.metadata, false),
+            AccountMeta::new_readonly(metaplex_token_metadata::id(), false),
+            AccountMeta::new_readonly(self.mint_points_override_address(pool, &nft.mint), false),
+            AccountMeta::new_readonly(
+                self.mint_list_entry_address(pool, &nft.mint, platform_state.mint_list_mode),
+                false,
+            ),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        if platform_state.stake_fee_lamports > 0 {
+            metas.push(AccountMeta::new(self.sol_treasury_address(pool), false));
+        }
+
+        self.protocol_fee_accounts(pool, &mut metas);
+        self.vesting_accounts(pool, staker, &mut metas);
+        self.loyalty_accounts(staker, Some(&nft.state), &mut metas);
+
+        metas
+    }
+
+    pub fn request_unstake(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        nft: &Nft,
+        break_lock: bool,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let platform_state = self.platform_state(pool);
+
+        let mut metas = vec![
+            AccountMeta::new(staker.user, true),
+            AccountMeta::new(pool.platform, false),
+            AccountMeta::new(staker.base_state, false),
+            AccountMeta::new(nft.state, false),
+            AccountMeta::new(nft.ata, false),
+            AccountMeta::new_readonly(nft.mint, false),
+            AccountMeta::new(reward_mint::id(), false),
+            AccountMeta::new(staker.reward_ata, false),
+            AccountMeta::new(pool.vault, false),
+            AccountMeta::new_readonly(pool.pda, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        self.protocol_fee_accounts(pool, &mut metas);
+        self.vesting_accounts(pool, staker, &mut metas);
+
+        if break_lock && platform_state.early_exit_penalty_to_treasury {
+            metas.push(AccountMeta::new(platform_state.penalty_treasury_ata, false));
+        }
+
+        self.loyalty_accounts(staker, Some(&nft.state), &mut metas);
+
+        self.process(vec![34, break_lock as u8], metas)
+    }
+
+    pub fn unstake_nft(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        nft: &Nft,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let mut metas = vec![
+            AccountMeta::new(staker.user, true),
+            AccountMeta::new(pool.platform, false),
+            AccountMeta::new(staker.base_state, false),
+            AccountMeta::new(nft.state, false),
+            AccountMeta::new(nft.ata, false),
+            AccountMeta::new_readonly(nft.mint, false),
+            AccountMeta::new(reward_mint::id(), false),
+            AccountMeta::new(staker.reward_ata, false),
+            AccountMeta::new(pool.vault, false),
+            AccountMeta::new_readonly(pool.pda, false),
+            AccountMeta::new_readonly(nft.metadata, false),
+            AccountMeta::new_readonly(metaplex_token_metadata::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        self.protocol_fee_accounts(pool, &mut metas);
+        self.vesting_accounts(pool, staker, &mut metas);
+        self.loyalty_accounts(staker, Some(&nft.state), &mut metas);
+
+        self.process(vec![2], metas)
+    }
+
+    pub fn complete_unstake(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        nft: &Nft,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        self.process(
+            vec![6],
+            vec![
+                AccountMeta::new(staker.user, true),
+                AccountMeta::new(pool.platform, false),
+                AccountMeta::new(staker.base_state, false),
+                AccountMeta::new(nft.state, false),
+                AccountMeta::new(nft.ata, false),
+                AccountMeta::new_readonly(nft.mint, false),
+                AccountMeta::new_readonly(pool.pda, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        )
+    }
+
+    pub fn claim(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        partner_token_accounts: &[Pubkey],
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let platform_state = self.platform_state(pool);
+
+        let mut metas = vec![
+            AccountMeta::new(staker.user, true),
+            AccountMeta::new(pool.platform, false),
+            AccountMeta::new(staker.base_state, false),
+            AccountMeta::new(reward_mint::id(), false),
+            AccountMeta::new(staker.reward_ata, false),
+            AccountMeta::new(pool.vault, false),
+            AccountMeta::new_readonly(pool.pda, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        self.protocol_fee_accounts(pool, &mut metas);
+        self.vesting_accounts(pool, staker, &mut metas);
+
+        let active_partner_vaults = platform_state
+            .partner_stream_vaults
+            .iter()
+            .take(usize::from(platform_state.partner_stream_count))
+            .filter(|vault| **vault != Pubkey::default());
+
+        for (partner_vault, partner_token_account) in
+            active_partner_vaults.zip(partner_token_accounts)
+        {
+            metas.push(AccountMeta::new(*partner_vault, false));
+            metas.push(AccountMeta::new(*partner_token_account, false));
+        }
+
+        self.loyalty_accounts(staker, None, &mut metas);
+
+        self.process(vec![3], metas)
+    }
+
+    pub fn set_reward_destination(
+        &mut self,
+        staker: &Staker,
+        destination: &Pubkey,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        self.process(
+            vec![4],
+            vec![
+                AccountMeta::new_readonly(staker.user, true),
+                AccountMeta::new(staker.base_state, false),
+                AccountMeta::new_readonly(reward_mint::id(), false),
+                AccountMeta::new_readonly(*destination, false),
+            ],
+        )
+    }
+
+    pub fn withdraw_vested(
+        &mut self,
+        pool: &Pool,
+        staker: &Staker,
+        destination: &Pubkey,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        self.process(
+            vec![28],
+            vec![
+                AccountMeta::new(staker.user, true),
+                AccountMeta::new(pool.platform, false),
+                AccountMeta::new_readonly(staker.base_state, false),
+                AccountMeta::new(self.vesting_schedule_address(pool, staker), false),
+                AccountMeta::new(reward_mint::id(), false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(pool.vault, false),
+                AccountMeta::new_readonly(pool.pda, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        )
+    }
+
+    pub fn admin_instruction(
+        &mut self,
+        pool: &Pool,
+        instruction_data: Vec<u8>,
+        extra_accounts: Vec<AccountMeta>,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        let mut metas = vec![
+            AccountMeta::new(admin::id(), true),
+            AccountMeta::new(pool.platform, false),
+        ];
+        metas.extend(extra_accounts);
+
+        self.process(instruction_data, metas)
+    }
+}
+
+pub fn instruction_data(instruction: u8, args: &[&[u8]]) -> Vec<u8> {
+    let mut data = vec![instruction];
+
+    for arg in args {
+        data.extend_from_slice(arg);
+    }
+
+    data
+}
@@ -0,0 +1,106 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError,
+        validation::{admin, reward_mint},
+    };
+    use solana_program::{instruction::AccountMeta, program_error::ProgramError};
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    fn distribute_bonus(
+        context: &mut TestContext,
+        pool: &Pool,
+        amount: u64,
+    ) -> Result<(), ProgramError> {
+        let admin_reward_ata = context.admin_reward_ata;
+
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(31, &[&amount.to_le_bytes()]),
+                vec![
+                    AccountMeta::new(reward_mint::id(), false),
+                    AccountMeta::new(admin_reward_ata, false),
+                    AccountMeta::new(pool.vault, false),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                ],
+            )
+            .map(|_| ())
+    }
+
+    #[test]
+    fn distribute_bonus_pays_stakers_by_points_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let locked_staker = context.create_staker(&pool);
+        let locked_nft = context.create_nft(&pool, &locked_staker, "Monkey RA");
+        let other_staker = context.create_staker(&pool);
+        let other_nft = context.create_nft(&pool, &other_staker, "Monkey RA");
+
+        context
+            .stake(&pool, &locked_staker, &locked_nft, 30 * 86400)
+            .unwrap();
+        context.stake(&pool, &other_staker, &other_nft, 0).unwrap();
+
+        let admin_reward_ata = context.admin_reward_ata;
+        context.set_token_account(
+            &admin_reward_ata,
+            &reward_mint::id(),
+            &admin::id(),
+            1_000 * TOKENS_PER_REWARD,
+        );
+        let vault_balance = context.token_balance(&pool.vault);
+
+        distribute_bonus(&mut context, &pool, 1_000).unwrap();
+
+        // 42 points in total: 22 for the locked NFT and 20 for the other one.
+        let reward_per_share: u64 = 1_000 * 100 / 42;
+        let distributed_reward = (reward_per_share * 42).div_ceil(100);
+
+        assert_eq!(
+            context.token_balance(&pool.vault),
+            vault_balance + 1_000 * TOKENS_PER_REWARD
+        );
+        assert_eq!(
+            context.platform_state(&pool).reward_per_share,
+            reward_per_share
+        );
+        assert_eq!(
+            context.platform_state(&pool).recycled_reward,
+            1_000 - distributed_reward
+        );
+
+        context.claim(&pool, &locked_staker, &[]).unwrap();
+        context.claim(&pool, &other_staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&locked_staker.reward_ata),
+            reward_per_share * 22 / 100 * TOKENS_PER_REWARD
+        );
+        assert_eq!(
+            context.token_balance(&other_staker.reward_ata),
+            reward_per_share * 20 / 100 * TOKENS_PER_REWARD
+        );
+    }
+
+    #[test]
+    fn distribute_bonus_rejects_minted_pool_test() {
+        let mut context = TestContext::new();
+        let pool = context
+            .initialize_platform("", 10, 1000, 1, 1_000 * TOKENS_PER_REWARD)
+            .unwrap();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        assert_eq!(
+            distribute_bonus(&mut context, &pool, 1_000),
+            Err(NFTStakingContractError::BonusRequiresVaultRewards.into())
+        );
+    }
+}
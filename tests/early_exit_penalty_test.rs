@@ -0,0 +1,158 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Nft, Pool, Staker, TestContext};
+    use nft_staking_contract::{events::NFTStakingContractEvent, validation::reward_mint};
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    const LOCK_DURATION_IN_SEC: u64 = 30 * 86400;
+
+    // Ten seconds of the default emission shared by two staked NFTs.
+    const REWARD_PER_SHARE: u64 = 50_000_000;
+
+    struct Stakers {
+        locked_staker: Staker,
+        locked_nft: Nft,
+        other_staker: Staker,
+    }
+
+    fn setup(context: &mut TestContext, pool: &Pool) -> Stakers {
+        let locked_staker = context.create_staker(pool);
+        let locked_nft = context.create_nft(pool, &locked_staker, "Monkey RA");
+        let other_staker = context.create_staker(pool);
+        let other_nft = context.create_nft(pool, &other_staker, "Monkey RA");
+
+        context
+            .stake(pool, &locked_staker, &locked_nft, LOCK_DURATION_IN_SEC)
+            .unwrap();
+        context.stake(pool, &other_staker, &other_nft, 0).unwrap();
+        context.warp(10);
+
+        Stakers {
+            locked_staker,
+            locked_nft,
+            other_staker,
+        }
+    }
+
+    fn forfeited_reward(events: &[NFTStakingContractEvent]) -> u64 {
+        events
+            .iter()
+            .find_map(|event| match event {
+                NFTStakingContractEvent::EarlyExitPenaltyPaid(penalty) => {
+                    Some(penalty.forfeited_reward)
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn early_exit_penalty_to_remaining_stakers_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+
+        context
+            .admin_instruction(&pool, instruction_data(8, &[&[50], &[0]]), vec![])
+            .unwrap();
+
+        let stakers = setup(&mut context, &pool);
+
+        // The locked NFT earns 22 points (20 base points times the 110% tier
+        // multiplier).
+        let pending_reward = REWARD_PER_SHARE * 22 / 100;
+        let other_pending_reward = REWARD_PER_SHARE * 20 / 100;
+
+        let events = context
+            .request_unstake(&pool, &stakers.locked_staker, &stakers.locked_nft, true)
+            .unwrap();
+
+        assert_eq!(forfeited_reward(&events), pending_reward / 2);
+        assert_eq!(
+            context.token_balance(&stakers.locked_staker.reward_ata),
+            (pending_reward - pending_reward / 2) * TOKENS_PER_REWARD
+        );
+        assert_eq!(
+            context.platform_state(&pool).reward_per_share,
+            REWARD_PER_SHARE + (pending_reward / 2) * 100 / 20
+        );
+
+        context.claim(&pool, &stakers.other_staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&stakers.other_staker.reward_ata),
+            (other_pending_reward + pending_reward / 2) * TOKENS_PER_REWARD
+        );
+    }
+
+    #[test]
+    fn early_exit_penalty_to_treasury_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let penalty_treasury_ata = Pubkey::new_unique();
+
+        context.set_token_account(
+            &penalty_treasury_ata,
+            &reward_mint::id(),
+            &Pubkey::new_unique(),
+            0,
+        );
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(8, &[&[50], &[1]]),
+                vec![
+                    AccountMeta::new_readonly(penalty_treasury_ata, false),
+                    AccountMeta::new_readonly(reward_mint::id(), false),
+                ],
+            )
+            .unwrap();
+
+        let stakers = setup(&mut context, &pool);
+        let pending_reward = REWARD_PER_SHARE * 22 / 100;
+
+        context
+            .request_unstake(&pool, &stakers.locked_staker, &stakers.locked_nft, true)
+            .unwrap();
+
+        assert_eq!(
+            context.token_balance(&penalty_treasury_ata),
+            (pending_reward / 2) * TOKENS_PER_REWARD
+        );
+        assert_eq!(
+            context.platform_state(&pool).reward_per_share,
+            REWARD_PER_SHARE
+        );
+    }
+
+    #[test]
+    fn early_exit_penalty_skips_claimed_rewards_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+
+        context
+            .admin_instruction(&pool, instruction_data(8, &[&[50], &[0]]), vec![])
+            .unwrap();
+
+        let stakers = setup(&mut context, &pool);
+        let pending_reward = REWARD_PER_SHARE * 22 / 100;
+
+        context.claim(&pool, &stakers.locked_staker, &[]).unwrap();
+        context.warp(10);
+
+        // Only the ten seconds accrued after the claim are subject to the
+        // penalty.
+        let events = context
+            .request_unstake(&pool, &stakers.locked_staker, &stakers.locked_nft, true)
+            .unwrap();
+
+        assert_eq!(forfeited_reward(&events), pending_reward / 2);
+        assert_eq!(
+            context.token_balance(&stakers.locked_staker.reward_ata),
+            (2 * pending_reward - pending_reward / 2) * TOKENS_PER_REWARD
+        );
+    }
+}
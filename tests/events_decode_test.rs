@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use nft_staking_contract::events::{Claimed, NFTStakingContractEvent, PROGRAM_DATA_LOG_PREFIX};
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn events_decode_test() {
+        let event = NFTStakingContractEvent::Claimed(Claimed {
+            user: Pubkey::new_unique(),
+            user_base_state: Pubkey::new_unique(),
+            user_reward_ata: Pubkey::new_unique(),
+            reward: 250,
+            token_amount: 250000000,
+            total_reward_claimed: 1000,
+            timestamp: 1656000000,
+        });
+
+        let logs = vec![
+            "Program log: Instruction: ClaimReward".to_string(),
+            format!(
+                "{}{}",
+                PROGRAM_DATA_LOG_PREFIX,
+                base64::encode(event.try_to_vec().unwrap())
+            ),
+            "Program data: bm90IGFuIGV2ZW50".to_string(),
+        ];
+
+        assert_eq!(NFTStakingContractEvent::from_logs(&logs), vec![event]);
+    }
+}
@@ -4,6 +4,16 @@ mod tests {
 
     #[test]
     fn instruction_data_unpack_test() {
-        let mut packed_data = [0, 5, 128, 81, 1, 0, 0, 0, 0, 0, 60, 0, 0, 0, 0, 0, 0, 0];
+        let packed_data = [0, 5, 128, 81, 1, 0, 0, 0, 0, 0, 60, 0, 0, 0, 0, 0, 0, 0];
+
+        match NFTStakingContractInstruction::unpack_instruction_data(&packed_data).unwrap() {
+            NFTStakingContractInstruction::InitializePlatform(data) => {
+                assert_eq!(data.percent, 5);
+                assert_eq!(data.distribution, 86400);
+                assert_eq!(data.accumulation, 60);
+                assert_eq!(data.reward_mint_cap, 0);
+            }
+            instruction => panic!("unexpected instruction {:?}", instruction),
+        }
     }
 }
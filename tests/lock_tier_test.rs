@@ -0,0 +1,50 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::TestContext;
+    use nft_staking_contract::error::NFTStakingContractError;
+
+    const LOCK_DURATION_IN_SEC: u64 = 30 * 86400;
+
+    #[test]
+    fn stake_rejects_lock_duration_outside_lock_tiers_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        assert_eq!(
+            context.stake(&pool, &staker, &nft, LOCK_DURATION_IN_SEC + 1),
+            Err(NFTStakingContractError::InvalidLockDuration.into())
+        );
+    }
+
+    #[test]
+    fn locked_nft_cannot_be_unstaked_before_unlock_test() {
+        let mut context = TestContext::new();
+        // A slow emission so the vault covers a whole lock period of rewards.
+        let pool = context.initialize_platform("", 1, 1_000_000, 1, 0).unwrap();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context
+            .stake(&pool, &staker, &nft, LOCK_DURATION_IN_SEC)
+            .unwrap();
+        context.warp(LOCK_DURATION_IN_SEC as i64 - 1);
+
+        assert_eq!(
+            context.request_unstake(&pool, &staker, &nft, false),
+            Err(NFTStakingContractError::NftLocked.into())
+        );
+        assert_eq!(
+            context.unstake_nft(&pool, &staker, &nft),
+            Err(NFTStakingContractError::NftLocked.into())
+        );
+
+        context.warp(1);
+        context.unstake_nft(&pool, &staker, &nft).unwrap();
+
+        assert_eq!(context.token_account(&nft.ata).owner, staker.user);
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Nft, Pool, Staker, TestContext};
+    use nft_staking_contract::{error::NFTStakingContractError, validation::reward_mint};
+    use solana_program::{instruction::AccountMeta, program_error::ProgramError};
+
+    const LOYALTY_STEP_IN_SEC: u64 = 100;
+
+    const LOYALTY_STEP_PERCENTAGE: u16 = 10;
+
+    const LOYALTY_MAX_PERCENTAGE: u16 = 50;
+
+    fn set_loyalty_curve(context: &mut TestContext, pool: &Pool) {
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(
+                    10,
+                    &[
+                        &LOYALTY_STEP_IN_SEC.to_le_bytes(),
+                        &LOYALTY_STEP_PERCENTAGE.to_le_bytes(),
+                        &LOYALTY_MAX_PERCENTAGE.to_le_bytes(),
+                    ],
+                ),
+                vec![],
+            )
+            .unwrap();
+    }
+
+    fn claim_with_nft_states(
+        context: &mut TestContext,
+        pool: &Pool,
+        staker: &Staker,
+        nfts: &[&Nft],
+    ) -> Result<(), ProgramError> {
+        let mut metas = vec![
+            AccountMeta::new(staker.user, true),
+            AccountMeta::new(pool.platform, false),
+            AccountMeta::new(staker.base_state, false),
+            AccountMeta::new(reward_mint::id(), false),
+            AccountMeta::new(staker.reward_ata, false),
+            AccountMeta::new(pool.vault, false),
+            AccountMeta::new_readonly(pool.pda, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+        metas.extend(nfts.iter().map(|nft| AccountMeta::new(nft.state, false)));
+
+        context.process(vec![3], metas).map(|_| ())
+    }
+
+    #[test]
+    fn loyalty_refresh_requires_every_staked_nft_state_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_loyalty_curve(&mut context, &pool);
+
+        context.stake(&pool, &staker, &first_nft, 0).unwrap();
+        context.stake(&pool, &staker, &second_nft, 0).unwrap();
+        context.warp(LOYALTY_STEP_IN_SEC as i64);
+
+        assert_eq!(
+            claim_with_nft_states(&mut context, &pool, &staker, &[&first_nft]),
+            Err(NFTStakingContractError::IncompleteUserNftStates.into())
+        );
+        assert_eq!(
+            claim_with_nft_states(&mut context, &pool, &staker, &[&first_nft, &first_nft]),
+            Err(NFTStakingContractError::IncompleteUserNftStates.into())
+        );
+
+        claim_with_nft_states(&mut context, &pool, &staker, &[&first_nft, &second_nft]).unwrap();
+
+        assert_eq!(context.nft_state(&first_nft).nft_points, 22);
+        assert_eq!(context.nft_state(&second_nft).nft_points, 22);
+        assert_eq!(context.base_state(&staker).total_nft_points, 44);
+    }
+
+    #[test]
+    fn request_unstake_refreshes_loyalty_points_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_loyalty_curve(&mut context, &pool);
+
+        context.stake(&pool, &staker, &first_nft, 0).unwrap();
+        context.stake(&pool, &staker, &second_nft, 0).unwrap();
+        context.warp(2 * LOYALTY_STEP_IN_SEC as i64);
+        context
+            .request_unstake(&pool, &staker, &first_nft, false)
+            .unwrap();
+
+        assert_eq!(context.nft_state(&second_nft).nft_points, 24);
+        assert_eq!(context.base_state(&staker).total_nft_points, 24);
+        assert_eq!(context.platform_state(&pool).total_nft_points, 24);
+    }
+}
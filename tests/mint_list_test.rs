@@ -0,0 +1,100 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Nft, Pool, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError,
+        state::{MINT_LIST_MODE_ALLOWLIST, MINT_LIST_MODE_DENYLIST},
+    };
+    use solana_program::{instruction::AccountMeta, system_program};
+
+    fn set_mint_list_mode(context: &mut TestContext, pool: &Pool, list: u8) {
+        context
+            .admin_instruction(pool, instruction_data(15, &[&[list]]), vec![])
+            .unwrap();
+    }
+
+    fn add_mint_list_entry(context: &mut TestContext, pool: &Pool, nft: &Nft, list: u8) {
+        let mint_list_entry = context.mint_list_entry_address(pool, &nft.mint, list);
+
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(16, &[&[list]]),
+                vec![
+                    AccountMeta::new_readonly(nft.mint, false),
+                    AccountMeta::new(mint_list_entry, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            )
+            .unwrap();
+    }
+
+    fn remove_mint_list_entry(context: &mut TestContext, pool: &Pool, nft: &Nft, list: u8) {
+        let mint_list_entry = context.mint_list_entry_address(pool, &nft.mint, list);
+
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(17, &[&[list]]),
+                vec![
+                    AccountMeta::new_readonly(nft.mint, false),
+                    AccountMeta::new(mint_list_entry, false),
+                ],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn allowlist_only_accepts_listed_mints_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let listed_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let other_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_mint_list_mode(&mut context, &pool, MINT_LIST_MODE_ALLOWLIST);
+        add_mint_list_entry(&mut context, &pool, &listed_nft, MINT_LIST_MODE_ALLOWLIST);
+
+        assert_eq!(
+            context.stake(&pool, &staker, &other_nft, 0),
+            Err(NFTStakingContractError::MintNotAllowlisted.into())
+        );
+
+        context.stake(&pool, &staker, &listed_nft, 0).unwrap();
+        context.unstake_nft(&pool, &staker, &listed_nft).unwrap();
+
+        remove_mint_list_entry(&mut context, &pool, &listed_nft, MINT_LIST_MODE_ALLOWLIST);
+
+        assert_eq!(
+            context.stake(&pool, &staker, &listed_nft, 0),
+            Err(NFTStakingContractError::MintNotAllowlisted.into())
+        );
+    }
+
+    #[test]
+    fn denylist_rejects_listed_mints_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let listed_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let other_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_mint_list_mode(&mut context, &pool, MINT_LIST_MODE_DENYLIST);
+        add_mint_list_entry(&mut context, &pool, &listed_nft, MINT_LIST_MODE_DENYLIST);
+
+        assert_eq!(
+            context.stake(&pool, &staker, &listed_nft, 0),
+            Err(NFTStakingContractError::MintDenylisted.into())
+        );
+
+        context.stake(&pool, &staker, &other_nft, 0).unwrap();
+
+        remove_mint_list_entry(&mut context, &pool, &listed_nft, MINT_LIST_MODE_DENYLIST);
+
+        context.stake(&pool, &staker, &listed_nft, 0).unwrap();
+
+        assert_eq!(context.base_state(&staker).total_staked_nfts, 2);
+    }
+}
@@ -0,0 +1,74 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Nft, Pool, TestContext};
+    use solana_program::{instruction::AccountMeta, system_program};
+
+    fn mint_points_override_accounts(
+        context: &TestContext,
+        pool: &Pool,
+        nft: &Nft,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(nft.mint, false),
+            AccountMeta::new(context.mint_points_override_address(pool, &nft.mint), false),
+        ]
+    }
+
+    #[test]
+    fn mint_points_override_replaces_rarity_points_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let other_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        let mut accounts = mint_points_override_accounts(&context, &pool, &nft);
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(12, &[&70u64.to_le_bytes()]),
+                accounts,
+            )
+            .unwrap();
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.stake(&pool, &staker, &other_nft, 0).unwrap();
+
+        assert_eq!(context.nft_state(&nft).nft_points, 70);
+        assert_eq!(context.nft_state(&other_nft).nft_points, 20);
+        assert_eq!(context.base_state(&staker).total_nft_points, 90);
+
+        // Changing the override only applies to the next stake of the mint.
+        let accounts = mint_points_override_accounts(&context, &pool, &nft);
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(13, &[&30u64.to_le_bytes()]),
+                accounts,
+            )
+            .unwrap();
+
+        assert_eq!(context.nft_state(&nft).nft_points, 70);
+
+        context.unstake_nft(&pool, &staker, &nft).unwrap();
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        assert_eq!(context.nft_state(&nft).nft_points, 30);
+
+        let accounts = mint_points_override_accounts(&context, &pool, &nft);
+        context
+            .admin_instruction(&pool, vec![14], accounts)
+            .unwrap();
+
+        assert!(!context.exists(&context.mint_points_override_address(&pool, &nft.mint)));
+
+        context.unstake_nft(&pool, &staker, &nft).unwrap();
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        assert_eq!(context.nft_state(&nft).nft_points, 20);
+        assert_eq!(context.base_state(&staker).total_nft_points, 40);
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Staker, TestContext, VAULT_DEPOSIT};
+    use nft_staking_contract::validation::{admin, reward_mint};
+
+    // One RA NFT alone in a pool emitting 10% of the supply over 1000 seconds
+    // earns this many reward units per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    #[test]
+    fn named_pools_keep_separate_state_test() {
+        let mut context = TestContext::new();
+        let first_pool = context
+            .initialize_platform("Alpha", 10, 1000, 1, 0)
+            .unwrap();
+
+        let admin_reward_ata = context.admin_reward_ata;
+        context.set_token_account(
+            &admin_reward_ata,
+            &reward_mint::id(),
+            &admin::id(),
+            VAULT_DEPOSIT,
+        );
+
+        let second_pool = context.initialize_platform("Beta", 20, 1000, 1, 0).unwrap();
+
+        assert_ne!(first_pool.platform, second_pool.platform);
+        assert_ne!(first_pool.vault, second_pool.vault);
+        assert!(context
+            .initialize_platform("Alpha", 10, 1000, 1, 0)
+            .is_err());
+
+        let first_staker = context.create_staker(&first_pool);
+        let second_staker = Staker {
+            base_state: context.user_base_state_address(&second_pool.platform, &first_staker.user),
+            ..first_staker
+        };
+        let first_nft = context.create_nft(&first_pool, &first_staker, "Monkey RA");
+        let second_nft = context.create_nft(&second_pool, &second_staker, "Monkey RA");
+
+        assert_ne!(first_staker.base_state, second_staker.base_state);
+
+        context
+            .stake(&first_pool, &first_staker, &first_nft, 0)
+            .unwrap();
+        context
+            .stake(&second_pool, &second_staker, &second_nft, 0)
+            .unwrap();
+
+        assert_eq!(context.platform_state(&first_pool).total_staked_nfts, 1);
+        assert_eq!(context.platform_state(&second_pool).total_staked_nfts, 1);
+        assert_eq!(context.base_state(&first_staker).total_staked_nfts, 1);
+        assert_eq!(context.base_state(&second_staker).total_staked_nfts, 1);
+
+        context.warp(10);
+        context.claim(&first_pool, &first_staker, &[]).unwrap();
+
+        let first_pool_reward = 10 * REWARD_PER_SEC * TOKENS_PER_REWARD;
+
+        assert_eq!(
+            context.token_balance(&first_staker.reward_ata),
+            first_pool_reward
+        );
+        assert_eq!(
+            context.token_balance(&first_pool.vault),
+            VAULT_DEPOSIT - first_pool_reward
+        );
+        assert_eq!(context.token_balance(&second_pool.vault), VAULT_DEPOSIT);
+
+        context.claim(&second_pool, &second_staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&second_staker.reward_ata),
+            first_pool_reward + 2 * first_pool_reward
+        );
+    }
+}
@@ -0,0 +1,245 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, Staker, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError,
+        events::{NFTStakingContractEvent, PartnerRewardStreamRemoved},
+        validation::admin,
+    };
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+
+    const PARTNER_EMISSION_PER_SEC: u64 = 1_000;
+
+    const PARTNER_VAULT_BALANCE: u64 = 1_000_000_000;
+
+    fn add_partner_reward_stream(context: &mut TestContext, pool: &Pool) -> Pubkey {
+        let partner_mint = Pubkey::new_unique();
+        let partner_vault = Pubkey::new_unique();
+
+        context.set_mint(&partner_mint, None, 0, PARTNER_VAULT_BALANCE);
+        context.set_token_account(
+            &partner_vault,
+            &partner_mint,
+            &pool.pda,
+            PARTNER_VAULT_BALANCE,
+        );
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(20, &[&PARTNER_EMISSION_PER_SEC.to_le_bytes()]),
+                vec![
+                    AccountMeta::new_readonly(partner_mint, false),
+                    AccountMeta::new_readonly(partner_vault, false),
+                    AccountMeta::new_readonly(pool.pda, false),
+                ],
+            )
+            .unwrap();
+
+        partner_mint
+    }
+
+    fn create_partner_token_account(
+        context: &mut TestContext,
+        staker: &Staker,
+        partner_mint: &Pubkey,
+    ) -> Pubkey {
+        let partner_token_account = Pubkey::new_unique();
+
+        context.set_token_account(&partner_token_account, partner_mint, &staker.user, 0);
+
+        partner_token_account
+    }
+
+    #[test]
+    fn partner_reward_stream_pays_emission_by_points_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let partner_mint = add_partner_reward_stream(&mut context, &pool);
+        let locked_staker = context.create_staker(&pool);
+        let locked_nft = context.create_nft(&pool, &locked_staker, "Monkey RA");
+        let other_staker = context.create_staker(&pool);
+        let other_nft = context.create_nft(&pool, &other_staker, "Monkey RA");
+        let locked_partner_account =
+            create_partner_token_account(&mut context, &locked_staker, &partner_mint);
+        let other_partner_account =
+            create_partner_token_account(&mut context, &other_staker, &partner_mint);
+
+        context
+            .stake(&pool, &locked_staker, &locked_nft, 30 * 86400)
+            .unwrap();
+        context.stake(&pool, &other_staker, &other_nft, 0).unwrap();
+        context.warp(10);
+
+        context
+            .claim(&pool, &locked_staker, &[locked_partner_account])
+            .unwrap();
+        context
+            .claim(&pool, &other_staker, &[other_partner_account])
+            .unwrap();
+
+        // 42 points in total: 22 for the locked NFT and 20 for the other one.
+        let reward_per_share = 10 * PARTNER_EMISSION_PER_SEC * 100 / 42;
+
+        assert_eq!(
+            context
+                .platform_state(&pool)
+                .partner_stream_reward_per_share[0],
+            reward_per_share
+        );
+        assert_eq!(
+            context.token_balance(&locked_partner_account),
+            reward_per_share * 22 / 100
+        );
+        assert_eq!(
+            context.token_balance(&other_partner_account),
+            reward_per_share * 20 / 100
+        );
+        assert!(
+            context.token_balance(&locked_partner_account)
+                + context.token_balance(&other_partner_account)
+                <= 10 * PARTNER_EMISSION_PER_SEC
+        );
+    }
+
+    #[test]
+    fn partner_reward_owed_keeps_user_base_state_open_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let partner_mint = add_partner_reward_stream(&mut context, &pool);
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let partner_token_account =
+            create_partner_token_account(&mut context, &staker, &partner_mint);
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context
+            .request_unstake(&pool, &staker, &nft, false)
+            .unwrap();
+
+        assert!(context.exists(&staker.base_state));
+        assert_eq!(
+            context.base_state(&staker).partner_stream_owed[0],
+            10 * PARTNER_EMISSION_PER_SEC
+        );
+
+        context
+            .claim(&pool, &staker, &[partner_token_account])
+            .unwrap();
+
+        assert_eq!(
+            context.token_balance(&partner_token_account),
+            10 * PARTNER_EMISSION_PER_SEC
+        );
+        assert_eq!(context.base_state(&staker).partner_stream_owed[0], 0);
+    }
+
+    #[test]
+    fn partner_reward_claim_pays_up_to_vault_balance_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let partner_mint = add_partner_reward_stream(&mut context, &pool);
+        let partner_vault = context.platform_state(&pool).partner_stream_vaults[0];
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let partner_token_account =
+            create_partner_token_account(&mut context, &staker, &partner_mint);
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context.set_token_account(&partner_vault, &partner_mint, &pool.pda, 4_000);
+
+        context
+            .claim(&pool, &staker, &[partner_token_account])
+            .unwrap();
+
+        assert!(context.token_balance(&staker.reward_ata) > 0);
+        assert_eq!(context.token_balance(&partner_token_account), 4_000);
+        assert_eq!(context.token_balance(&partner_vault), 0);
+        assert_eq!(
+            context.base_state(&staker).partner_stream_owed[0],
+            10 * PARTNER_EMISSION_PER_SEC - 4_000
+        );
+
+        context
+            .claim(&pool, &staker, &[partner_token_account])
+            .unwrap();
+
+        assert_eq!(context.token_balance(&partner_token_account), 4_000);
+        assert_eq!(
+            context.base_state(&staker).partner_stream_owed[0],
+            10 * PARTNER_EMISSION_PER_SEC - 4_000
+        );
+    }
+
+    #[test]
+    fn remove_partner_reward_stream_frees_slot_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let partner_mint = add_partner_reward_stream(&mut context, &pool);
+        let partner_vault = context.platform_state(&pool).partner_stream_vaults[0];
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+
+        let events = context
+            .admin_instruction(&pool, instruction_data(35, &[&[0]]), vec![])
+            .unwrap();
+
+        assert!(
+            events.contains(&NFTStakingContractEvent::PartnerRewardStreamRemoved(
+                PartnerRewardStreamRemoved {
+                    platform: pool.platform,
+                    admin: admin::id(),
+                    stream: 0,
+                    mint: partner_mint,
+                    vault: partner_vault,
+                }
+            ))
+        );
+
+        let platform_state = context.platform_state(&pool);
+
+        assert_eq!(platform_state.partner_stream_count, 1);
+        assert_eq!(platform_state.partner_stream_mints[0], Pubkey::default());
+        assert_eq!(platform_state.partner_stream_emission_per_sec[0], 0);
+        assert_eq!(
+            context.admin_instruction(&pool, instruction_data(35, &[&[0]]), vec![]),
+            Err(NFTStakingContractError::InvalidPartnerRewardStream.into())
+        );
+        assert_eq!(
+            context.admin_instruction(
+                &pool,
+                instruction_data(21, &[&[0], &PARTNER_EMISSION_PER_SEC.to_le_bytes()]),
+                vec![],
+            ),
+            Err(NFTStakingContractError::InvalidPartnerRewardStream.into())
+        );
+
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(context.base_state(&staker).partner_stream_owed[0], 0);
+
+        let new_partner_mint = add_partner_reward_stream(&mut context, &pool);
+        let new_partner_token_account =
+            create_partner_token_account(&mut context, &staker, &new_partner_mint);
+        let platform_state = context.platform_state(&pool);
+
+        assert_eq!(platform_state.partner_stream_count, 1);
+        assert_eq!(platform_state.partner_stream_mints[0], new_partner_mint);
+
+        context.warp(10);
+        context
+            .claim(&pool, &staker, &[new_partner_token_account])
+            .unwrap();
+
+        assert_eq!(
+            context.token_balance(&new_partner_token_account),
+            10 * PARTNER_EMISSION_PER_SEC
+        );
+    }
+}
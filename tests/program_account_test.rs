@@ -0,0 +1,36 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::TestContext;
+    use nft_staking_contract::error::NFTStakingContractError;
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+
+    #[test]
+    fn stake_rejects_fake_program_accounts_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        let metas = context.stake_metas(&pool, &staker, &nft);
+
+        // Positions of the program and sysvar accounts in StakeNFT.
+        let cases = [
+            (12, NFTStakingContractError::InvalidMetadataProgram),
+            (15, NFTStakingContractError::InvalidTokenProgram),
+            (16, NFTStakingContractError::InvalidRentSysvar),
+            (17, NFTStakingContractError::InvalidAssociatedTokenProgram),
+            (18, NFTStakingContractError::InvalidSystemProgram),
+        ];
+
+        for (position, error) in cases {
+            let mut fake_metas = metas.clone();
+            fake_metas[position] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+            assert_eq!(context.process(vec![1], fake_metas), Err(error.into()));
+        }
+
+        context.process(vec![1], metas).unwrap();
+    }
+}
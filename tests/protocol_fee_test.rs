@@ -0,0 +1,169 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError, events::NFTStakingContractEvent,
+        state::MAX_PROTOCOL_FEE_BPS, validation::reward_mint,
+    };
+    use solana_program::{instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey};
+
+    const PROTOCOL_FEE_BPS: u16 = 500;
+
+    // One RA NFT alone in the default emission earns this many reward units
+    // per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    const PARTNER_EMISSION_PER_SEC: u64 = 1_000;
+
+    const PARTNER_VAULT_BALANCE: u64 = 1_000_000_000;
+
+    fn set_protocol_fee(
+        context: &mut TestContext,
+        pool: &Pool,
+        fee_bps: u16,
+    ) -> Result<Vec<NFTStakingContractEvent>, ProgramError> {
+        context.admin_instruction(
+            pool,
+            instruction_data(23, &[&fee_bps.to_le_bytes()]),
+            vec![],
+        )
+    }
+
+    fn set_protocol_fee_treasury(context: &mut TestContext, pool: &Pool) -> Pubkey {
+        let protocol_fee_treasury_ata = Pubkey::new_unique();
+
+        context.set_token_account(
+            &protocol_fee_treasury_ata,
+            &reward_mint::id(),
+            &Pubkey::new_unique(),
+            0,
+        );
+        context
+            .admin_instruction(
+                pool,
+                vec![24],
+                vec![
+                    AccountMeta::new_readonly(protocol_fee_treasury_ata, false),
+                    AccountMeta::new_readonly(reward_mint::id(), false),
+                ],
+            )
+            .unwrap();
+
+        protocol_fee_treasury_ata
+    }
+
+    #[test]
+    fn protocol_fee_is_taken_from_reward_payouts_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        assert_eq!(
+            set_protocol_fee(&mut context, &pool, PROTOCOL_FEE_BPS),
+            Err(NFTStakingContractError::InvalidProtocolFee.into())
+        );
+
+        let protocol_fee_treasury_ata = set_protocol_fee_treasury(&mut context, &pool);
+
+        assert_eq!(
+            set_protocol_fee(&mut context, &pool, MAX_PROTOCOL_FEE_BPS + 1),
+            Err(NFTStakingContractError::InvalidProtocolFee.into())
+        );
+
+        set_protocol_fee(&mut context, &pool, PROTOCOL_FEE_BPS).unwrap();
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+
+        let events = context.claim(&pool, &staker, &[]).unwrap();
+
+        let token_amount = 10 * REWARD_PER_SEC * TOKENS_PER_REWARD;
+        let protocol_fee = token_amount * u64::from(PROTOCOL_FEE_BPS) / 10_000;
+
+        let protocol_fee_paid = events
+            .iter()
+            .find_map(|event| match event {
+                NFTStakingContractEvent::ProtocolFeePaid(protocol_fee_paid) => {
+                    Some(protocol_fee_paid)
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(protocol_fee_paid.token_amount, protocol_fee);
+        assert_eq!(
+            protocol_fee_paid.protocol_fee_treasury_ata,
+            protocol_fee_treasury_ata
+        );
+        assert_eq!(
+            context.token_balance(&protocol_fee_treasury_ata),
+            protocol_fee
+        );
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            token_amount - protocol_fee
+        );
+    }
+
+    #[test]
+    fn protocol_fee_exempts_partner_reward_payouts_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let partner_mint = Pubkey::new_unique();
+        let partner_vault = Pubkey::new_unique();
+        let partner_token_account = Pubkey::new_unique();
+
+        context.set_mint(&partner_mint, None, 0, PARTNER_VAULT_BALANCE);
+        context.set_token_account(
+            &partner_vault,
+            &partner_mint,
+            &pool.pda,
+            PARTNER_VAULT_BALANCE,
+        );
+        context.set_token_account(&partner_token_account, &partner_mint, &staker.user, 0);
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(20, &[&PARTNER_EMISSION_PER_SEC.to_le_bytes()]),
+                vec![
+                    AccountMeta::new_readonly(partner_mint, false),
+                    AccountMeta::new_readonly(partner_vault, false),
+                    AccountMeta::new_readonly(pool.pda, false),
+                ],
+            )
+            .unwrap();
+
+        let protocol_fee_treasury_ata = set_protocol_fee_treasury(&mut context, &pool);
+
+        set_protocol_fee(&mut context, &pool, PROTOCOL_FEE_BPS).unwrap();
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context
+            .claim(&pool, &staker, &[partner_token_account])
+            .unwrap();
+
+        let token_amount = 10 * REWARD_PER_SEC * TOKENS_PER_REWARD;
+        let protocol_fee = token_amount * u64::from(PROTOCOL_FEE_BPS) / 10_000;
+
+        assert_eq!(
+            context.token_balance(&protocol_fee_treasury_ata),
+            protocol_fee
+        );
+        assert_eq!(
+            context.token_balance(&partner_token_account),
+            10 * PARTNER_EMISSION_PER_SEC
+        );
+        assert_eq!(
+            context.token_balance(&partner_vault),
+            PARTNER_VAULT_BALANCE - 10 * PARTNER_EMISSION_PER_SEC
+        );
+    }
+}
@@ -0,0 +1,150 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError,
+        state::{NO_RARITY_TIER, RARITY_TIERS},
+        validation::Validator,
+    };
+    use solana_program::{hash::hashv, pubkey::Pubkey};
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[&[1], &left, &right]).to_bytes()
+    }
+
+    fn set_rarity_merkle_root(context: &mut TestContext, pool: &Pool, root: [u8; 32]) {
+        context
+            .admin_instruction(pool, instruction_data(19, &[&root]), vec![])
+            .unwrap();
+    }
+
+    fn stake_with_proof_data(points: u64, tier: u8, proof: &[[u8; 32]]) -> Vec<u8> {
+        instruction_data(
+            1,
+            &[
+                &0u64.to_le_bytes(),
+                &points.to_le_bytes(),
+                &[tier],
+                &[proof.len() as u8],
+                &proof.concat(),
+            ],
+        )
+    }
+
+    #[test]
+    fn rarity_proof_test() {
+        let mints = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let points = [10, 50, 100];
+        let tiers = [0, 2, NO_RARITY_TIER];
+
+        let leaves: Vec<[u8; 32]> = mints
+            .iter()
+            .zip(points.iter().zip(tiers.iter()))
+            .map(|(mint, (points, tier))| Validator::rarity_merkle_leaf(mint, *points, *tier))
+            .collect();
+
+        let node = hash_pair(leaves[0], leaves[1]);
+        let root = hash_pair(node, leaves[2]);
+
+        assert_eq!(
+            Validator::validate_rarity_proof(&root, &mints[0], 10, 0, &[leaves[1], leaves[2]]),
+            Ok(())
+        );
+        assert_eq!(
+            Validator::validate_rarity_proof(&root, &mints[2], 100, NO_RARITY_TIER, &[node]),
+            Ok(())
+        );
+        assert_eq!(
+            Validator::validate_rarity_proof(&root, &mints[0], 100, 0, &[leaves[1], leaves[2]]),
+            Err(NFTStakingContractError::InvalidRarityProof.into())
+        );
+        assert_eq!(
+            Validator::validate_rarity_proof(&root, &mints[0], 10, 3, &[leaves[1], leaves[2]]),
+            Err(NFTStakingContractError::InvalidRarityProof.into())
+        );
+        assert_eq!(
+            Validator::validate_rarity_proof(&root, &mints[1], 50, 2, &[leaves[2]]),
+            Err(NFTStakingContractError::InvalidRarityProof.into())
+        );
+        assert_eq!(
+            Validator::validate_rarity_proof(&leaves[1], &mints[1], 50, 2, &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn stake_with_rarity_proof_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey CO");
+
+        let leaf = Validator::rarity_merkle_leaf(&nft.mint, 50, 2);
+        let other_leaf = Validator::rarity_merkle_leaf(&Pubkey::new_unique(), 10, 0);
+
+        set_rarity_merkle_root(&mut context, &pool, hash_pair(leaf, other_leaf));
+
+        let metas = context.stake_metas(&pool, &staker, &nft);
+
+        assert_eq!(
+            context.stake(&pool, &staker, &nft, 0),
+            Err(NFTStakingContractError::MissingRarityProof.into())
+        );
+        assert_eq!(
+            context.process(stake_with_proof_data(100, 2, &[other_leaf]), metas.clone()),
+            Err(NFTStakingContractError::InvalidRarityProof.into())
+        );
+        assert_eq!(
+            context.process(stake_with_proof_data(50, 3, &[other_leaf]), metas.clone()),
+            Err(NFTStakingContractError::InvalidRarityProof.into())
+        );
+        assert_eq!(
+            context.process(
+                stake_with_proof_data(50, RARITY_TIERS as u8, &[other_leaf]),
+                metas.clone()
+            ),
+            Err(NFTStakingContractError::InvalidArgs.into())
+        );
+
+        context
+            .process(stake_with_proof_data(50, 2, &[other_leaf]), metas)
+            .unwrap();
+
+        // The proven points and tier win over the "CO" code in the metadata
+        // name.
+        assert_eq!(context.nft_state(&nft).nft_points, 50);
+        assert_eq!(context.nft_state(&nft).rarity_tier, 2);
+        assert_eq!(context.base_state(&staker).rarity_counts, [0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn stake_with_single_leaf_rarity_tree_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey");
+
+        set_rarity_merkle_root(
+            &mut context,
+            &pool,
+            Validator::rarity_merkle_leaf(&nft.mint, 30, NO_RARITY_TIER),
+        );
+
+        let metas = context.stake_metas(&pool, &staker, &nft);
+
+        context
+            .process(stake_with_proof_data(30, NO_RARITY_TIER, &[]), metas)
+            .unwrap();
+
+        assert_eq!(context.nft_state(&nft).nft_points, 30);
+        assert_eq!(context.nft_state(&nft).rarity_tier, NO_RARITY_TIER);
+        assert_eq!(context.base_state(&staker).rarity_counts, [0; RARITY_TIERS]);
+    }
+}
@@ -0,0 +1,32 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::TestContext;
+    use nft_staking_contract::{error::NFTStakingContractError, state::UserNFTState};
+    use solana_program::{program_pack::Pack, rent::Rent};
+
+    #[test]
+    fn underfunded_user_nft_state_is_rejected_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context.set_lamports(
+            &nft.state,
+            Rent::default().minimum_balance(UserNFTState::LEN) - 1,
+        );
+
+        assert_eq!(
+            context.claim(&pool, &staker, &[]),
+            Err(NFTStakingContractError::StateAccountNotRentExempt.into())
+        );
+        assert_eq!(
+            context.request_unstake(&pool, &staker, &nft, false),
+            Err(NFTStakingContractError::StateAccountNotRentExempt.into())
+        );
+    }
+}
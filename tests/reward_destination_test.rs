@@ -0,0 +1,101 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Staker, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError,
+        events::{NFTStakingContractEvent, RewardDestinationChanged},
+        validation::reward_mint,
+    };
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+
+    // One RA NFT alone in the default emission earns this many reward units
+    // per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    // Position of the user reward ATA in StakeNFT.
+    const STAKE_USER_REWARD_ATA: usize = 8;
+
+    #[test]
+    fn stake_rejects_non_canonical_reward_ata_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let other_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let reward_account = Pubkey::new_unique();
+
+        context.set_token_account(&reward_account, &reward_mint::id(), &staker.user, 0);
+
+        let mut metas = context.stake_metas(&pool, &staker, &nft);
+        metas[STAKE_USER_REWARD_ATA] = AccountMeta::new(reward_account, false);
+
+        assert_eq!(
+            context.process(vec![1], metas),
+            Err(NFTStakingContractError::RewardAtaNotCanonical.into())
+        );
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        let mut metas = context.stake_metas(&pool, &staker, &other_nft);
+        metas[STAKE_USER_REWARD_ATA] = AccountMeta::new(reward_account, false);
+
+        assert_eq!(
+            context.process(vec![1], metas),
+            Err(NFTStakingContractError::RewardAtaMismatch.into())
+        );
+    }
+
+    #[test]
+    fn set_reward_destination_redirects_rewards_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let destination = Pubkey::new_unique();
+
+        context.set_token_account(&destination, &reward_mint::id(), &Pubkey::new_unique(), 0);
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+
+        let events = context
+            .set_reward_destination(&staker, &destination)
+            .unwrap();
+
+        assert_eq!(
+            events,
+            vec![NFTStakingContractEvent::RewardDestinationChanged(
+                RewardDestinationChanged {
+                    user: staker.user,
+                    user_base_state: staker.base_state,
+                    old_reward_destination: staker.reward_ata,
+                    new_reward_destination: destination,
+                    timestamp: context.now(),
+                }
+            )]
+        );
+        assert_eq!(context.base_state(&staker).user_reward_ata, destination);
+        assert_eq!(
+            context.claim(&pool, &staker, &[]),
+            Err(NFTStakingContractError::RewardAtaMismatch.into())
+        );
+
+        let redirected_staker = Staker {
+            user: staker.user,
+            base_state: staker.base_state,
+            reward_ata: destination,
+        };
+
+        context.claim(&pool, &redirected_staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&destination),
+            10 * REWARD_PER_SEC * TOKENS_PER_REWARD
+        );
+        assert_eq!(context.token_balance(&staker.reward_ata), 0);
+    }
+}
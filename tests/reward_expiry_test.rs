@@ -0,0 +1,119 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, Staker, TestContext};
+    use nft_staking_contract::events::NFTStakingContractEvent;
+    use solana_program::instruction::AccountMeta;
+
+    const CLAIM_WINDOW_IN_SEC: u64 = 86400;
+
+    // One accumulation period per claim window; a RA NFT sharing the pool
+    // with another one earns this many reward units per period.
+    const REWARD_PER_WINDOW: u64 = 86_400_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    fn expire_rewards(
+        context: &mut TestContext,
+        pool: &Pool,
+        stakers: &[&Staker],
+    ) -> Vec<NFTStakingContractEvent> {
+        let mut metas = vec![AccountMeta::new(pool.platform, false)];
+        metas.extend(
+            stakers
+                .iter()
+                .map(|staker| AccountMeta::new(staker.base_state, false)),
+        );
+
+        context.process(vec![30], metas).unwrap()
+    }
+
+    fn expired_rewards(events: &[NFTStakingContractEvent]) -> Vec<(u64, u64)> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                NFTStakingContractEvent::RewardsExpired(rewards_expired) => Some((
+                    rewards_expired.expired_reward,
+                    rewards_expired.remaining_reward,
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expired_rewards_are_recycled_to_stakers_by_points_test() {
+        let mut context = TestContext::new();
+        let pool = context
+            .initialize_platform("", 1, 100_000, CLAIM_WINDOW_IN_SEC, 0)
+            .unwrap();
+        let claiming_staker = context.create_staker(&pool);
+        let claiming_nft = context.create_nft(&pool, &claiming_staker, "Monkey RA");
+        let idle_staker = context.create_staker(&pool);
+        let idle_nft = context.create_nft(&pool, &idle_staker, "Monkey RA");
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(29, &[&CLAIM_WINDOW_IN_SEC.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+        context
+            .stake(&pool, &claiming_staker, &claiming_nft, 0)
+            .unwrap();
+        context.stake(&pool, &idle_staker, &idle_nft, 0).unwrap();
+
+        // The first crank only checkpoints what is pending: nothing is older
+        // than a full claim window yet.
+        context.warp(CLAIM_WINDOW_IN_SEC as i64);
+        let events = expire_rewards(&mut context, &pool, &[&claiming_staker, &idle_staker]);
+
+        assert!(expired_rewards(&events).is_empty());
+        assert_eq!(
+            context.base_state(&idle_staker).expiry_checkpoint_reward,
+            REWARD_PER_WINDOW
+        );
+
+        context.claim(&pool, &claiming_staker, &[]).unwrap();
+
+        assert_eq!(
+            context
+                .base_state(&claiming_staker)
+                .expiry_checkpoint_reward,
+            0
+        );
+
+        // The idle staker forfeits the checkpointed window; the claiming
+        // staker has nothing left from before its claim.
+        context.warp(CLAIM_WINDOW_IN_SEC as i64);
+        let events = expire_rewards(&mut context, &pool, &[&claiming_staker, &idle_staker]);
+
+        assert_eq!(
+            expired_rewards(&events),
+            vec![(REWARD_PER_WINDOW, REWARD_PER_WINDOW)]
+        );
+        assert_eq!(
+            context.platform_state(&pool).recycled_reward,
+            REWARD_PER_WINDOW
+        );
+
+        // The recycled reward is spread over the 40 staked points at the next
+        // pool update.
+        context.claim(&pool, &claiming_staker, &[]).unwrap();
+
+        assert_eq!(context.platform_state(&pool).recycled_reward, 0);
+        assert_eq!(
+            context.token_balance(&claiming_staker.reward_ata),
+            (2 * REWARD_PER_WINDOW + REWARD_PER_WINDOW / 2) * TOKENS_PER_REWARD
+        );
+
+        context.claim(&pool, &idle_staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&idle_staker.reward_ata),
+            (REWARD_PER_WINDOW + REWARD_PER_WINDOW / 2) * TOKENS_PER_REWARD
+        );
+    }
+}
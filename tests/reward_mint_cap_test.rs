@@ -0,0 +1,119 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, TestContext};
+    use nft_staking_contract::validation::reward_mint;
+
+    // One RA NFT (20 points) alone in the default emission earns 2_000_000
+    // reward units, 2_000_000_000_000 tokens, per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    const REWARD_MINT_CAP: u64 = 5 * TOKENS_PER_REWARD * 1_000_000;
+
+    #[test]
+    fn reward_mint_cap_keeps_remainder_owed_test() {
+        let mut context = TestContext::new();
+        let pool = context
+            .initialize_platform("", 10, 1000, 1, REWARD_MINT_CAP)
+            .unwrap();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let initial_supply = context.mint_supply(&reward_mint::id());
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        let earned_reward = 10 * REWARD_PER_SEC;
+        let minted_reward = REWARD_MINT_CAP / TOKENS_PER_REWARD;
+
+        assert_eq!(context.token_balance(&staker.reward_ata), REWARD_MINT_CAP);
+        assert_eq!(
+            context.mint_supply(&reward_mint::id()),
+            initial_supply + REWARD_MINT_CAP
+        );
+        assert_eq!(
+            context.platform_state(&pool).total_reward_minted,
+            REWARD_MINT_CAP
+        );
+        assert_eq!(
+            context.base_state(&staker).reward_owed,
+            earned_reward - minted_reward
+        );
+        assert_eq!(
+            context.base_state(&staker).total_reward_claimed,
+            minted_reward
+        );
+
+        context
+            .request_unstake(&pool, &staker, &nft, false)
+            .unwrap();
+
+        assert!(context.exists(&staker.base_state));
+        assert_eq!(
+            context.base_state(&staker).reward_owed,
+            earned_reward - minted_reward
+        );
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(22, &[&(100 * REWARD_MINT_CAP).to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            earned_reward * TOKENS_PER_REWARD
+        );
+        assert_eq!(context.base_state(&staker).reward_owed, 0);
+        assert_eq!(
+            context.platform_state(&pool).total_reward_minted,
+            earned_reward * TOKENS_PER_REWARD
+        );
+    }
+
+    #[test]
+    fn reward_mint_cap_reserves_vested_rewards_test() {
+        let mut context = TestContext::new();
+        let pool = context
+            .initialize_platform("", 10, 1000, 1, REWARD_MINT_CAP)
+            .unwrap();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(27, &[&100u64.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(context.token_balance(&staker.reward_ata), 0);
+        assert_eq!(
+            context.platform_state(&pool).total_reward_minted,
+            REWARD_MINT_CAP
+        );
+
+        context.warp(100);
+        context
+            .withdraw_vested(&pool, &staker, &staker.reward_ata)
+            .unwrap();
+
+        assert_eq!(context.token_balance(&staker.reward_ata), REWARD_MINT_CAP);
+        assert_eq!(
+            context.platform_state(&pool).total_reward_minted,
+            REWARD_MINT_CAP
+        );
+    }
+}
@@ -0,0 +1,53 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, TestContext};
+    use nft_staking_contract::events::NFTStakingContractEvent;
+
+    const SET_BONUS_MULTIPLIER: u16 = 150;
+
+    #[test]
+    fn set_bonus_follows_complete_rarity_set_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nfts: Vec<_> = ["Monkey CO", "Monkey RA", "Monkey EP", "Monkey LE"]
+            .iter()
+            .map(|name| context.create_nft(&pool, &staker, name))
+            .collect();
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(11, &[&SET_BONUS_MULTIPLIER.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+
+        for nft in &nfts[..3] {
+            context.stake(&pool, &staker, nft, 0).unwrap();
+        }
+
+        assert_eq!(context.base_state(&staker).set_bonus_points, 0);
+        assert_eq!(context.base_state(&staker).total_nft_points, 80);
+
+        let events = context.stake(&pool, &staker, &nfts[3], 0).unwrap();
+
+        // 180 points for one NFT of each rarity, plus half of it as a bonus.
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, NFTStakingContractEvent::SetBonusUpdated(_))));
+        assert_eq!(context.base_state(&staker).set_bonus_points, 90);
+        assert_eq!(context.base_state(&staker).total_nft_points, 270);
+        assert_eq!(context.platform_state(&pool).total_nft_points, 270);
+
+        context
+            .request_unstake(&pool, &staker, &nfts[0], false)
+            .unwrap();
+
+        assert_eq!(context.base_state(&staker).set_bonus_points, 0);
+        assert_eq!(context.base_state(&staker).total_nft_points, 170);
+        assert_eq!(context.platform_state(&pool).total_nft_points, 170);
+    }
+}
@@ -0,0 +1,78 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::{error::NFTStakingContractError, events::NFTStakingContractEvent};
+    use solana_program::{
+        instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey, system_program,
+    };
+
+    const STAKE_FEE_LAMPORTS: u64 = 10_000_000;
+
+    fn withdraw_treasury_sol(
+        context: &mut TestContext,
+        pool: &Pool,
+        destination: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), ProgramError> {
+        let sol_treasury = context.sol_treasury_address(pool);
+
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(26, &[&lamports.to_le_bytes()]),
+                vec![
+                    AccountMeta::new(sol_treasury, false),
+                    AccountMeta::new(*destination, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            )
+            .map(|_| ())
+    }
+
+    #[test]
+    fn stake_fee_is_collected_and_withdrawn_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let sol_treasury = context.sol_treasury_address(&pool);
+        let destination = Pubkey::new_unique();
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(25, &[&STAKE_FEE_LAMPORTS.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+
+        let events = context.stake(&pool, &staker, &first_nft, 0).unwrap();
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            NFTStakingContractEvent::StakeFeePaid(stake_fee_paid)
+                if stake_fee_paid.lamports == STAKE_FEE_LAMPORTS
+        )));
+
+        context.stake(&pool, &staker, &second_nft, 0).unwrap();
+
+        assert_eq!(context.lamports(&sol_treasury), 2 * STAKE_FEE_LAMPORTS);
+        assert_eq!(
+            withdraw_treasury_sol(
+                &mut context,
+                &pool,
+                &destination,
+                2 * STAKE_FEE_LAMPORTS + 1
+            ),
+            Err(NFTStakingContractError::InsufficientSolTreasuryBalance.into())
+        );
+
+        withdraw_treasury_sol(&mut context, &pool, &destination, STAKE_FEE_LAMPORTS).unwrap();
+
+        assert_eq!(context.lamports(&sol_treasury), STAKE_FEE_LAMPORTS);
+        assert_eq!(context.lamports(&destination), STAKE_FEE_LAMPORTS);
+    }
+}
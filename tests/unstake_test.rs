@@ -0,0 +1,126 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, TestContext};
+    use nft_staking_contract::{error::NFTStakingContractError, events::NFTStakingContractEvent};
+
+    const COOLDOWN_IN_SEC: u64 = 3600;
+
+    // One RA NFT (20 points) alone in the default emission earns this many
+    // reward units per second.
+    const REWARD_PER_SEC: u64 = 2_000_000;
+
+    const TOKENS_PER_REWARD: u64 = 1_000_000;
+
+    fn set_unstake_cooldown(context: &mut TestContext, pool: &Pool) {
+        context
+            .admin_instruction(
+                pool,
+                instruction_data(7, &[&COOLDOWN_IN_SEC.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn unstake_cooldown_keeps_user_base_state_until_complete_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_unstake_cooldown(&mut context, &pool);
+
+        context.stake(&pool, &staker, &first_nft, 0).unwrap();
+        context.stake(&pool, &staker, &second_nft, 0).unwrap();
+        context.warp(10);
+        context
+            .request_unstake(&pool, &staker, &first_nft, false)
+            .unwrap();
+        context
+            .request_unstake(&pool, &staker, &second_nft, false)
+            .unwrap();
+
+        let reward_balance = context.token_balance(&staker.reward_ata);
+
+        assert_eq!(reward_balance, 10 * REWARD_PER_SEC * TOKENS_PER_REWARD);
+        assert_eq!(context.base_state(&staker).total_staked_nfts, 0);
+        assert_eq!(context.base_state(&staker).unbonding_nfts, 2);
+        assert_eq!(
+            context.complete_unstake(&pool, &staker, &first_nft),
+            Err(NFTStakingContractError::UnstakeCooldownActive.into())
+        );
+
+        context.warp(COOLDOWN_IN_SEC as i64);
+        context
+            .complete_unstake(&pool, &staker, &first_nft)
+            .unwrap();
+
+        assert!(!context.exists(&first_nft.state));
+        assert_eq!(context.token_account(&first_nft.ata).owner, staker.user);
+        assert_eq!(context.base_state(&staker).unbonding_nfts, 1);
+
+        let events = context
+            .complete_unstake(&pool, &staker, &second_nft)
+            .unwrap();
+
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, NFTStakingContractEvent::UserAccountClosed(_))));
+        assert!(!context.exists(&staker.base_state));
+        assert_eq!(context.token_account(&second_nft.ata).owner, staker.user);
+        assert_eq!(context.token_balance(&staker.reward_ata), reward_balance);
+    }
+
+    #[test]
+    fn unstake_nft_returns_nft_without_cooldown_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+
+        let events = context.unstake_nft(&pool, &staker, &nft).unwrap();
+
+        let unstaked = events
+            .iter()
+            .find_map(|event| match event {
+                NFTStakingContractEvent::Unstaked(unstaked) => Some(unstaked),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(unstaked.nft_mint, nft.mint);
+        assert_eq!(unstaked.nft_points, 20);
+        assert_eq!(unstaked.user_total_staked_nfts, 0);
+        assert_eq!(unstaked.platform_total_staked_nfts, 0);
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            10 * REWARD_PER_SEC * TOKENS_PER_REWARD
+        );
+        assert_eq!(context.token_account(&nft.ata).owner, staker.user);
+        assert!(!context.exists(&nft.state));
+        assert!(!context.exists(&staker.base_state));
+    }
+
+    #[test]
+    fn unstake_nft_rejected_with_cooldown_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        set_unstake_cooldown(&mut context, &pool);
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+
+        assert_eq!(
+            context.unstake_nft(&pool, &staker, &nft),
+            Err(NFTStakingContractError::UnstakeCooldownEnabled.into())
+        );
+    }
+}
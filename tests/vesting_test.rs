@@ -0,0 +1,119 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, Pool, Staker, TestContext};
+    use nft_staking_contract::{
+        error::NFTStakingContractError, events::NFTStakingContractEvent, validation::reward_mint,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    const VESTING_PERIOD_IN_SEC: u64 = 100;
+
+    fn setup() -> (TestContext, Pool, Staker) {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(27, &[&VESTING_PERIOD_IN_SEC.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+
+        (context, pool, staker)
+    }
+
+    fn vested_token_amount(events: &[NFTStakingContractEvent]) -> u64 {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                NFTStakingContractEvent::RewardVested(reward_vested) => {
+                    Some(reward_vested.token_amount)
+                }
+                _ => None,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn vesting_applies_to_every_payout_test() {
+        let (mut context, pool, staker) = setup();
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey CO");
+
+        context.stake(&pool, &staker, &first_nft, 0).unwrap();
+        context.warp(10);
+
+        let stake_events = context.stake(&pool, &staker, &second_nft, 0).unwrap();
+        context.warp(10);
+
+        let claim_events = context.claim(&pool, &staker, &[]).unwrap();
+        context.warp(10);
+
+        let unstake_events = context
+            .request_unstake(&pool, &staker, &first_nft, false)
+            .unwrap();
+
+        let stake_vested = vested_token_amount(&stake_events);
+        let claim_vested = vested_token_amount(&claim_events);
+        let unstake_vested = vested_token_amount(&unstake_events);
+
+        assert!(stake_vested > 0);
+        assert!(claim_vested > 0);
+        assert!(unstake_vested > 0);
+        assert_eq!(context.token_balance(&staker.reward_ata), 0);
+
+        let vesting_schedule = context.vesting_schedule(&pool, &staker);
+        assert_eq!(
+            vesting_schedule.locked_amount + vesting_schedule.unlocked_amount,
+            stake_vested + claim_vested + unstake_vested
+        );
+
+        context.warp(VESTING_PERIOD_IN_SEC as i64);
+        context
+            .withdraw_vested(&pool, &staker, &staker.reward_ata)
+            .unwrap();
+
+        assert_eq!(
+            context.token_balance(&staker.reward_ata),
+            stake_vested + claim_vested + unstake_vested
+        );
+    }
+
+    #[test]
+    fn withdraw_vested_follows_reward_destination_test() {
+        let (mut context, pool, staker) = setup();
+        let nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let destination = Pubkey::new_unique();
+
+        context.set_token_account(&destination, &reward_mint::id(), &Pubkey::new_unique(), 0);
+
+        context.stake(&pool, &staker, &nft, 0).unwrap();
+        context.warp(10);
+        context.claim(&pool, &staker, &[]).unwrap();
+        context
+            .set_reward_destination(&staker, &destination)
+            .unwrap();
+        context.warp(VESTING_PERIOD_IN_SEC as i64);
+
+        assert_eq!(
+            context.withdraw_vested(&pool, &staker, &staker.reward_ata),
+            Err(NFTStakingContractError::RewardAtaMismatch.into())
+        );
+
+        context
+            .withdraw_vested(&pool, &staker, &destination)
+            .unwrap();
+
+        let vesting_schedule = context.vesting_schedule(&pool, &staker);
+        assert!(vesting_schedule.total_withdrawn > 0);
+        assert_eq!(
+            context.token_balance(&destination),
+            vesting_schedule.total_withdrawn
+        );
+        assert_eq!(context.token_balance(&staker.reward_ata), 0);
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{instruction_data, TestContext};
+
+    const WARM_UP_PERIOD_IN_SEC: u64 = 100;
+
+    #[test]
+    fn warm_up_is_tracked_per_nft_test() {
+        let mut context = TestContext::new();
+        let pool = context.default_pool();
+        let staker = context.create_staker(&pool);
+        let first_nft = context.create_nft(&pool, &staker, "Monkey RA");
+        let second_nft = context.create_nft(&pool, &staker, "Monkey RA");
+
+        context
+            .admin_instruction(
+                &pool,
+                instruction_data(9, &[&WARM_UP_PERIOD_IN_SEC.to_le_bytes()]),
+                vec![],
+            )
+            .unwrap();
+
+        context.stake(&pool, &staker, &first_nft, 0).unwrap();
+
+        assert_eq!(context.base_state(&staker).total_nft_points, 0);
+        assert_eq!(context.base_state(&staker).pending_nft_points, 20);
+
+        // Staking a second NFT must not push back the end of the first
+        // NFT's warm-up.
+        context.warp(60);
+        context.stake(&pool, &staker, &second_nft, 0).unwrap();
+
+        assert_eq!(
+            context.nft_state(&first_nft).warm_up_until,
+            context.nft_state(&second_nft).warm_up_until - 60
+        );
+        assert_eq!(context.base_state(&staker).pending_nft_points, 40);
+
+        context.warp(40);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(context.nft_state(&first_nft).warm_up_until, 0);
+        assert_ne!(context.nft_state(&second_nft).warm_up_until, 0);
+        assert_eq!(context.base_state(&staker).total_nft_points, 20);
+        assert_eq!(context.base_state(&staker).pending_nft_points, 20);
+        assert_eq!(context.token_balance(&staker.reward_ata), 0);
+
+        context.warp(60);
+        context.claim(&pool, &staker, &[]).unwrap();
+
+        assert_eq!(context.nft_state(&second_nft).warm_up_until, 0);
+        assert_eq!(context.base_state(&staker).total_nft_points, 40);
+        assert_eq!(context.base_state(&staker).pending_nft_points, 0);
+        assert_ne!(context.token_balance(&staker.reward_ata), 0);
+    }
+}